@@ -17,13 +17,17 @@
 use crate::{Constant, Variable};
 
 use conlang_errors::{InputError, Result};
+use conlang_span::{Span, Symbol};
 
 use indexmap::IndexMap;
+use serde_json::Value;
 
 /// The [`Assignment`] data type represents an input assignment to a conlang program.
 #[derive(Clone, Debug, Default)]
 pub struct Assignment {
-    pub map: IndexMap<String, Constant>,
+    pub map: IndexMap<Symbol, Constant>,
+    /// The span of each assigned variable.
+    pub spans: IndexMap<Symbol, Span>,
 }
 
 impl Assignment {
@@ -33,11 +37,38 @@ impl Assignment {
         let mut assignment = Assignment::default();
         // Add each input to the assignment, checking for duplicates.
         for (variable, constant) in inputs {
-            let span = variable.span;
-            if assignment.map.insert(variable.name, constant).is_some() {
-                return Err(InputError::duplicate_input_variable(span).into());
+            if let Some(first) = assignment.spans.get(&variable.name) {
+                return Err(InputError::duplicate_input_variable(&variable, *first, variable.span).into());
             }
+            assignment.map.insert(variable.name, constant);
+            assignment.spans.insert(variable.name, variable.span);
         }
         Ok(assignment)
     }
+
+    /// Returns a new [`Assignment`] from a JSON object that maps variables to values, e.g., `{"x": "2", "y": 3}`.
+    /// The variables and constants of the assignment have no spans, since they do not come from a source file.
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let object = value.as_object().ok_or_else(|| InputError::json_assignment_not_an_object(value))?;
+
+        let mut inputs = Vec::with_capacity(object.len());
+        for (name, value) in object {
+            let string = match value {
+                Value::String(string) => string.clone(),
+                Value::Number(number) if number.is_u64() || number.is_i64() => number.to_string(),
+                _ => return Err(InputError::invalid_json_input_value(name, value).into()),
+            };
+            let constant =
+                Constant::new(string, Span::dummy()).map_err(|_| InputError::invalid_json_input_value(name, value))?;
+            inputs.push((Variable::new(name), constant));
+        }
+
+        Self::new(inputs)
+    }
+
+    /// Returns a new [`Assignment`] from a string containing a JSON object, see [`Assignment::from_json`].
+    pub fn from_json_string(string: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(string).map_err(InputError::invalid_json_assignment)?;
+        Self::from_json(&value)
+    }
 }
@@ -0,0 +1,117 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Constraint, LinearCombination, Program, Term, Variable};
+
+/// A [`ProgramBuilder`] constructs a [`Program`] in code, without parsing a source file.
+/// Variables are combined with the arithmetic operators into linear combinations,
+/// and each call to [`ProgramBuilder::enforce`] adds a constraint `(A) * (B) = (C);`.
+/// The nodes of the program have no source location, i.e., their spans are [`Span::dummy`](conlang_span::Span::dummy).
+///
+/// For example, the following builds the program `(x) * (1 + x) = (y);`:
+/// ```rust
+/// use conlang_ast::{Constant, ProgramBuilder};
+///
+/// # conlang_span::session_globals::create_session_if_not_set_then(|_| {
+/// let mut builder = ProgramBuilder::new();
+/// let x = builder.var("x");
+/// let y = builder.var("y");
+/// builder.enforce(x.clone(), x + Constant::from(1), y);
+///
+/// let program = builder.build();
+/// assert_eq!(program.to_string(), "(x) * (1 + x) = (y);\n");
+/// # });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ProgramBuilder {
+    program: Program,
+}
+
+impl ProgramBuilder {
+    /// Returns a builder for an empty program.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the term `1 * name`, for use in the constraints of the program.
+    pub fn var(&self, name: impl AsRef<str>) -> Term {
+        Term::from(Variable::new(name))
+    }
+
+    /// Adds the constraint `(a) * (b) = (c);` to the program.
+    pub fn enforce(
+        &mut self,
+        a: impl Into<LinearCombination>,
+        b: impl Into<LinearCombination>,
+        c: impl Into<LinearCombination>,
+    ) -> &mut Self {
+        self.program.constraints.push(Constraint::new(a.into(), b.into(), c.into()));
+        self
+    }
+
+    /// Returns the program built so far.
+    pub fn build(self) -> Program {
+        self.program
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Constant, ProgramBuilder};
+    use conlang_span::{session_globals::create_session_if_not_set_then, Span};
+
+    #[test]
+    fn test_arithmetic() {
+        create_session_if_not_set_then(|_| {
+            let mut builder = ProgramBuilder::new();
+            let (x, y, z) = (builder.var("x"), builder.var("y"), builder.var("z"));
+
+            builder
+                .enforce(x.clone() * Constant::from(2) - y.clone(), Constant::from(3) - z.clone(), -z.clone())
+                .enforce(Constant::from(5), (x.clone() + y.clone()) * Constant::from(4), x - (y + z));
+
+            // The terms of a sum are kept in order, and are not combined.
+            assert_eq!(
+                builder.build().to_string(),
+                "(2 * x - y) * (3 - z) = (-1 * z);\n(5) * (4 * x + 4 * y) = (x - y - z);\n"
+            );
+        });
+    }
+
+    #[test]
+    fn test_variables() {
+        create_session_if_not_set_then(|_| {
+            let mut builder = ProgramBuilder::new();
+            let x = builder.var("x");
+            builder.enforce(x.clone(), x.clone(), builder.var("y"));
+            builder.enforce(x.clone(), builder.var("y"), builder.var("x"));
+
+            // The same name refers to the same variable.
+            let program = builder.build();
+            assert_eq!(program.variable_names().iter().map(|name| name.as_str()).collect::<Vec<_>>(), ["x", "y"]);
+
+            // Built programs have no source location.
+            assert!(program.span.is_dummy());
+            assert!(program.constraints.iter().all(|constraint| constraint.span.is_dummy()));
+            assert!(program.variables().all(|variable| variable.span == Span::dummy()));
+        });
+    }
+
+    #[test]
+    fn test_empty_program() {
+        assert!(ProgramBuilder::new().build().constraints.is_empty());
+    }
+}
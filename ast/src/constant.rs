@@ -19,15 +19,19 @@ use conlang_span::Span;
 
 use snarkvm_console::{network::Testnet3, prelude::Zero, types::Field};
 
-use snarkvm_console::prelude::One;
-use std::str::FromStr;
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use snarkvm_console::prelude::{Inverse, One};
+use std::{fmt, str::FromStr};
 
 /// The [`Constant`] data type represents a constant in a linear combination.
 /// A constant can either be standalone or part of a term.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Constant {
+    #[serde(deserialize_with = "deserialize_value")]
     pub value: Field<Testnet3>,
+    #[serde(default)]
     pub repr: String,
+    #[serde(default, with = "conlang_span::span_json")]
     pub span: Span,
 }
 
@@ -45,6 +49,26 @@ impl Constant {
         self.repr = format!("-{}", self.repr);
     }
 
+    /// Returns whether the constant is zero.
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    /// Returns whether the constant is one.
+    pub fn is_one(&self) -> bool {
+        self.value.is_one()
+    }
+
+    /// Returns whether the constant reads better as a negated value, e.g., `-1` rather than the field modulus minus one.
+    pub fn is_negative(&self) -> bool {
+        (-self.value).to_string().len() < self.value.to_string().len()
+    }
+
+    /// Returns the multiplicative inverse of the constant, or `None` if it is zero.
+    pub fn inverse(&self) -> Option<Self> {
+        self.value.inverse().ok().map(Self::from)
+    }
+
     /// Return the one constant.
     pub fn one() -> Self {
         Self { value: Field::<Testnet3>::one(), repr: "1".to_string(), span: Span::default() }
@@ -56,3 +80,34 @@ impl Default for Constant {
         Self { value: Field::<Testnet3>::zero(), repr: "0".to_string(), span: Span::default() }
     }
 }
+
+impl From<Field<Testnet3>> for Constant {
+    /// Returns a constant with the given value, without a source location.
+    fn from(value: Field<Testnet3>) -> Self {
+        Self { value, repr: value.to_string(), span: Span::dummy() }
+    }
+}
+
+impl From<u64> for Constant {
+    /// Returns a constant with the given value, without a source location.
+    fn from(value: u64) -> Self {
+        Self::from(Field::<Testnet3>::from_u64(value))
+    }
+}
+
+impl fmt::Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Field elements are displayed with a `field` suffix, which is not part of the syntax.
+        let (sign, value) = if self.is_negative() { ("-", -self.value) } else { ("", self.value) };
+        let value = value.to_string();
+        write!(f, "{sign}{}", value.strip_suffix("field").unwrap_or(&value))
+    }
+}
+
+/// Deserializes the value of a constant from a string, with or without the `field` suffix, e.g., `"2"` or `"2field"`.
+fn deserialize_value<'de, D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Field<Testnet3>, D::Error> {
+    let string = String::deserialize(deserializer)?;
+    let value = string.strip_suffix("field").unwrap_or(&string);
+    Field::<Testnet3>::from_str(&format!("{value}field"))
+        .map_err(|_| D::Error::custom(format!("`{string}` is not a field element")))
+}
@@ -14,14 +14,47 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::LinearCombination;
+use crate::{ops::covering_span, LinearCombination, Provenance};
+
+use conlang_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// The [`Constraint`] data type represents a constraint.
 /// A constraint is composed of three linear combinations.
 /// A constraint has the form: `A * B = C` where `A`, `B`, and `C` are linear combinations.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Constraint {
     pub a: LinearCombination,
     pub b: LinearCombination,
     pub c: LinearCombination,
+    /// The span of the source constraint, which the passes keep when they change the constraint.
+    #[serde(default, with = "conlang_span::span_json")]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Provenance::is_empty")]
+    pub provenance: Provenance,
+}
+
+impl Constraint {
+    /// Returns a new constraint `(A) * (B) = (C)`.
+    /// Its span covers those of the linear combinations.
+    pub fn new(a: LinearCombination, b: LinearCombination, c: LinearCombination) -> Self {
+        let span = covering_span([a.span, b.span, c.span]);
+        Self { a, b, c, span, provenance: Provenance::default() }
+    }
+}
+
+impl fmt::Display for Constraint {
+    /// The alternate form `{:#}` adds the provenance of a changed constraint as a comment.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}) * ({}) = ({});", self.a, self.b, self.c)?;
+        if f.alternate() && !self.provenance.is_empty() {
+            match self.span.is_dummy() {
+                true => write!(f, " // {}", self.provenance)?,
+                false => write!(f, " // from {}; {}", self.span, self.provenance)?,
+            }
+        }
+        Ok(())
+    }
 }
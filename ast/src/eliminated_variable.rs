@@ -0,0 +1,39 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{LinearCombination, Variable};
+
+use conlang_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The [`EliminatedVariable`] data type represents a variable that an optimization removed from the constraints.
+/// Its value is recovered from the linear combination it equals, which only uses variables that remain in the program.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EliminatedVariable {
+    pub variable: Variable,
+    pub value: LinearCombination,
+    /// The span of the constraint the variable was eliminated with.
+    #[serde(default, with = "conlang_span::span_json")]
+    pub span: Span,
+}
+
+impl fmt::Display for EliminatedVariable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.variable, self.value)
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Variable;
+
+use conlang_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The [`Import`] data type represents an instance of another program, e.g., `import sha256 as sha (input = x, digest = y);`.
+///
+/// The variables of the imported program are renamed under the prefix, e.g., `w` becomes `sha.w`,
+/// except for its ports, which are connected to variables of the importing program instead.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Import {
+    /// The name of the imported package.
+    pub package: String,
+    /// The prefix of the variables of the imported program.
+    pub prefix: String,
+    /// The variables of the imported program that are connected to variables of the importing program.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<Port>,
+    #[serde(default, with = "conlang_span::span_json")]
+    pub span: Span,
+}
+
+impl Import {
+    /// Returns the import of the `package` under the `prefix`, without any ports.
+    pub fn new(package: impl Into<String>, prefix: impl Into<String>) -> Self {
+        Self { package: package.into(), prefix: prefix.into(), ports: Vec::new(), span: Span::dummy() }
+    }
+
+    /// Returns the import with the variable `inner` of the imported program connected to the variable `outer`.
+    pub fn with_port(mut self, inner: &str, outer: &str) -> Self {
+        self.ports.push(Port { inner: Variable::new(inner), outer: Variable::new(outer) });
+        self
+    }
+}
+
+impl fmt::Display for Import {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "import {} as {}", self.package, self.prefix)?;
+        if !self.ports.is_empty() {
+            let ports = self.ports.iter().map(|port| port.to_string()).collect::<Vec<_>>();
+            write!(f, " ({})", ports.join(", "))?;
+        }
+        write!(f, ";")
+    }
+}
+
+/// The [`Port`] data type connects a variable of an imported program to a variable of the importing program.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Port {
+    /// The variable of the imported program.
+    pub inner: Variable,
+    /// The variable of the importing program.
+    pub outer: Variable,
+}
+
+impl fmt::Display for Port {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.inner, self.outer)
+    }
+}
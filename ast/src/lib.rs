@@ -16,6 +16,9 @@
 
 //! The abstract syntax tree (AST) for a conlang program.
 
+pub mod builder;
+pub use builder::*;
+
 pub mod constant;
 pub use constant::*;
 
@@ -25,12 +28,23 @@ pub use constraint::*;
 pub mod assignment;
 pub use assignment::*;
 
+pub mod eliminated_variable;
+pub use eliminated_variable::*;
+
+pub mod import;
+pub use import::*;
+
 pub mod linear_combination;
 pub use linear_combination::*;
 
+mod ops;
+
 pub mod program;
 pub use program::*;
 
+pub mod provenance;
+pub use provenance::*;
+
 pub mod term;
 pub use term::*;
 
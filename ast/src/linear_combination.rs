@@ -14,7 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Constant, Term};
+use crate::{ops::covering_span, Constant, Term};
+
+use conlang_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// The [`LinearCombination`] data type represents a linear combination.
 /// A linear combination is *sum* of terms and constants.
@@ -24,8 +29,47 @@ use crate::{Constant, Term};
 /// - `-x`
 /// - `x - 15y`
 /// - `-0 + 3z`
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct LinearCombination {
     pub terms: Vec<Term>,
+    #[serde(default)]
     pub constant: Constant,
+    #[serde(default, with = "conlang_span::span_json")]
+    pub span: Span,
+}
+
+impl LinearCombination {
+    /// Returns a new linear combination, the sum of the `terms`, with a zero constant.
+    /// Its span covers those of the `terms`.
+    pub fn from_terms(terms: impl IntoIterator<Item = Term>) -> Self {
+        let terms: Vec<_> = terms.into_iter().collect();
+        let span = covering_span(terms.iter().map(|term| term.span));
+        Self { terms, constant: Constant::default(), span }
+    }
+}
+
+impl fmt::Display for LinearCombination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The constant is omitted if it is zero, unless there is nothing else to display.
+        let mut is_first = true;
+        if !self.constant.is_zero() || self.terms.is_empty() {
+            write!(f, "{}", self.constant)?;
+            is_first = false;
+        }
+
+        for term in self.terms.iter() {
+            if is_first {
+                write!(f, "{term}")?;
+            } else if term.constant.is_negative() {
+                let mut term = term.clone();
+                term.negate();
+                write!(f, " - {term}")?;
+            } else {
+                write!(f, " + {term}")?;
+            }
+            is_first = false;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,214 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Arithmetic on constants, terms, and linear combinations, used to build programs in code.
+//!
+//! Sums and differences are [`LinearCombination`]s, and the only product is scaling by a [`Constant`],
+//! since a product of two variables is not linear. The terms of a sum are not combined.
+
+use crate::{Constant, LinearCombination, Term, Variable};
+
+use conlang_span::Span;
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Returns the smallest span covering the `spans` that have a source location, or a dummy span if there are none.
+/// The result of an operation covers the spans of its operands.
+pub(crate) fn covering_span(spans: impl IntoIterator<Item = Span>) -> Span {
+    spans.into_iter().filter(|span| !span.is_dummy()).reduce(|lhs, rhs| lhs + rhs).unwrap_or_default()
+}
+
+impl From<Constant> for LinearCombination {
+    fn from(constant: Constant) -> Self {
+        Self { terms: Vec::new(), span: constant.span, constant }
+    }
+}
+
+impl From<Term> for LinearCombination {
+    fn from(term: Term) -> Self {
+        Self::from_terms([term])
+    }
+}
+
+impl From<Variable> for Term {
+    fn from(variable: Variable) -> Self {
+        Self::new(Constant::one(), variable)
+    }
+}
+
+impl From<Variable> for LinearCombination {
+    fn from(variable: Variable) -> Self {
+        Self::from(Term::from(variable))
+    }
+}
+
+impl Neg for Constant {
+    type Output = Constant;
+
+    fn neg(mut self) -> Self::Output {
+        self.negate();
+        self
+    }
+}
+
+impl Add for Constant {
+    type Output = Constant;
+
+    fn add(self, rhs: Constant) -> Self::Output {
+        Constant::from(self.value + rhs.value)
+    }
+}
+
+impl Sub for Constant {
+    type Output = Constant;
+
+    fn sub(self, rhs: Constant) -> Self::Output {
+        Constant::from(self.value - rhs.value)
+    }
+}
+
+impl Mul for Constant {
+    type Output = Constant;
+
+    fn mul(self, rhs: Constant) -> Self::Output {
+        Constant::from(self.value * rhs.value)
+    }
+}
+
+impl Mul<Term> for Constant {
+    type Output = Term;
+
+    fn mul(self, rhs: Term) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Mul<LinearCombination> for Constant {
+    type Output = LinearCombination;
+
+    fn mul(self, rhs: LinearCombination) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Add<Term> for Constant {
+    type Output = LinearCombination;
+
+    fn add(self, rhs: Term) -> Self::Output {
+        LinearCombination::from(self) + rhs
+    }
+}
+
+impl Add<LinearCombination> for Constant {
+    type Output = LinearCombination;
+
+    fn add(self, rhs: LinearCombination) -> Self::Output {
+        LinearCombination::from(self) + rhs
+    }
+}
+
+impl Sub<Term> for Constant {
+    type Output = LinearCombination;
+
+    fn sub(self, rhs: Term) -> Self::Output {
+        LinearCombination::from(self) - rhs
+    }
+}
+
+impl Sub<LinearCombination> for Constant {
+    type Output = LinearCombination;
+
+    fn sub(self, rhs: LinearCombination) -> Self::Output {
+        LinearCombination::from(self) - rhs
+    }
+}
+
+impl Neg for Term {
+    type Output = Term;
+
+    fn neg(mut self) -> Self::Output {
+        self.negate();
+        self
+    }
+}
+
+impl Mul<Constant> for Term {
+    type Output = Term;
+
+    fn mul(self, rhs: Constant) -> Self::Output {
+        Term { constant: self.constant * rhs, ..self }
+    }
+}
+
+impl<T: Into<LinearCombination>> Add<T> for Term {
+    type Output = LinearCombination;
+
+    fn add(self, rhs: T) -> Self::Output {
+        LinearCombination::from(self) + rhs
+    }
+}
+
+impl<T: Into<LinearCombination>> Sub<T> for Term {
+    type Output = LinearCombination;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        LinearCombination::from(self) - rhs
+    }
+}
+
+impl Neg for LinearCombination {
+    type Output = LinearCombination;
+
+    fn neg(self) -> Self::Output {
+        LinearCombination {
+            terms: self.terms.into_iter().map(Neg::neg).collect(),
+            constant: -self.constant,
+            span: self.span,
+        }
+    }
+}
+
+impl Mul<Constant> for LinearCombination {
+    type Output = LinearCombination;
+
+    fn mul(self, rhs: Constant) -> Self::Output {
+        LinearCombination {
+            terms: self.terms.into_iter().map(|term| term * rhs.clone()).collect(),
+            constant: self.constant * rhs,
+            span: self.span,
+        }
+    }
+}
+
+impl<T: Into<LinearCombination>> Add<T> for LinearCombination {
+    type Output = LinearCombination;
+
+    fn add(mut self, rhs: T) -> Self::Output {
+        let rhs = rhs.into();
+        self.terms.extend(rhs.terms);
+        self.constant = self.constant + rhs.constant;
+        self.span = covering_span([self.span, rhs.span]);
+        self
+    }
+}
+
+impl<T: Into<LinearCombination>> Sub<T> for LinearCombination {
+    type Output = LinearCombination;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        self + -rhs.into()
+    }
+}
@@ -14,11 +14,121 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::Constraint;
+use crate::{Constraint, EliminatedVariable, Import, Variable};
+use conlang_errors::{AstError, Result};
+use conlang_span::{Span, Symbol};
+
+use indexmap::IndexSet;
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, io::BufWriter, path::PathBuf};
 
 /// The [`Program`] type represents a conlang program as a series of recursive data types.
 /// These data types form a tree that begins from a [`Program`] type root.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Program {
+    /// The programs instantiated by this program, which are linked into it before compilation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<Import>,
     pub constraints: Vec<Constraint>,
+    /// The variables removed from the constraints by optimizations, in order of elimination.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub eliminated: Vec<EliminatedVariable>,
+    #[serde(default, with = "conlang_span::span_json")]
+    pub span: Span,
+}
+
+impl Program {
+    /// Returns an iterator over every occurrence of a variable in the program, including the eliminated variables.
+    pub fn variables(&self) -> impl Iterator<Item = &Variable> {
+        let constraint_variables = self
+            .constraints
+            .iter()
+            .flat_map(|constraint| [&constraint.a, &constraint.b, &constraint.c])
+            .flat_map(|linear_combination| linear_combination.terms.iter())
+            .map(|term| &term.variable);
+        let eliminated_variables = self.eliminated.iter().flat_map(|eliminated| {
+            std::iter::once(&eliminated.variable).chain(eliminated.value.terms.iter().map(|term| &term.variable))
+        });
+        constraint_variables.chain(eliminated_variables)
+    }
+
+    /// Returns the names of the variables used in the program, in order of first occurrence.
+    pub fn variable_names(&self) -> IndexSet<Symbol> {
+        self.variables().map(|variable| variable.name).collect()
+    }
+
+    /// Serializes the program into a JSON string.
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self).map_err(|e| AstError::failed_to_convert_ast_to_json_string(&e))?)
+    }
+
+    /// Deserializes a program from a JSON string.
+    pub fn from_json_string(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json).map_err(|e| AstError::failed_to_read_json_string_to_ast(&e))?)
+    }
+
+    /// Deserializes a program from the JSON file at `path`.
+    pub fn from_json_file(path: PathBuf) -> Result<Self> {
+        let data = fs::read_to_string(&path).map_err(|e| AstError::failed_to_read_json_file(&path, &e))?;
+        Self::from_json_string(&data)
+    }
+
+    /// Serializes the program into a JSON file named `file_name` in the directory `path`.
+    pub fn to_json_file(&self, mut path: PathBuf, file_name: &str) -> Result<()> {
+        path.push(file_name);
+        let file = fs::File::create(&path).map_err(|e| AstError::failed_to_create_ast_json_file(&path, &e))?;
+        let writer = BufWriter::new(file);
+        Ok(serde_json::to_writer_pretty(writer, &self)
+            .map_err(|e| AstError::failed_to_write_ast_to_json_file(&path, &e))?)
+    }
+}
+
+impl fmt::Display for Program {
+    /// The alternate form `{:#}` adds the provenance of changed constraints as comments.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for import in self.imports.iter() {
+            writeln!(f, "{import}")?;
+        }
+        for constraint in self.constraints.iter() {
+            match f.alternate() {
+                true => writeln!(f, "{constraint:#}")?,
+                false => writeln!(f, "{constraint}")?,
+            }
+        }
+        // Eliminated variables are not part of the syntax, so they are shown as comments.
+        for eliminated in self.eliminated.iter() {
+            writeln!(f, "// {eliminated}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Program;
+    use conlang_span::session_globals::create_session_if_not_set_then;
+
+    #[test]
+    fn test_from_json_string() {
+        create_session_if_not_set_then(|_| {
+            // Spans, constants, and coefficients of one may be omitted, and field elements need no suffix.
+            let json = r#"{"constraints": [{
+                "a": {"terms": [{"constant": {"value": "2"}, "variable": {"name": "x"}}]},
+                "b": {"terms": [{"variable": {"name": "y"}}], "constant": {"value": "1field"}},
+                "c": {"terms": [{"variable": {"name": "z"}, "span": {"span": {"lo": 1, "hi": 2}}}]}
+            }]}"#;
+            let program = Program::from_json_string(json).unwrap();
+            assert_eq!(program.to_string(), "(2 * x) * (1 + y) = (z);\n");
+            assert_eq!(program.constraints[0].c.terms[0].span.hi.0, 2);
+
+            // Serialized programs are loaded as they were.
+            assert_eq!(Program::from_json_string(&program.to_json_string().unwrap()).unwrap(), program);
+
+            let err = Program::from_json_string(r#"{"constraints": [], "span": {}}"#).unwrap_err();
+            assert!(err.to_string().contains("missing field `span`"), "{err}");
+            let json = r#"{"constraints": [{"a": {"terms": []}, "b": {"terms": []}, "c": {"constant": {"value": "x"}}}]}"#;
+            let err = Program::from_json_string(json).unwrap_err();
+            assert!(err.to_string().contains("`x` is not a field element"), "{err}");
+        });
+    }
 }
@@ -0,0 +1,70 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use conlang_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The [`Provenance`] of a constraint records how the compiler passes derived it from the source.
+///
+/// A constraint always comes from the source constraint at its own span.
+/// The provenance adds the other source constraints it was derived from, e.g., those that defined
+/// an eliminated variable, and the passes that changed it.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// The other source constraints the constraint was derived from, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<Span>,
+    /// The names of the passes that changed the constraint, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub passes: Vec<String>,
+}
+
+impl Provenance {
+    /// Returns whether the constraint is unchanged from the source.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty() && self.passes.is_empty()
+    }
+
+    /// Records that the constraint was derived from the source constraint at `span`, if it has a source location.
+    pub fn record_source(&mut self, span: Span) {
+        if !span.is_dummy() && !self.sources.contains(&span) {
+            self.sources.push(span);
+        }
+    }
+
+    /// Records that the pass called `name` changed the constraint.
+    pub fn record_pass(&mut self, name: &str) {
+        if self.passes.last().map_or(true, |last| last != name) {
+            self.passes.push(name.to_string());
+        }
+    }
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.sources.is_empty() {
+            let sources = self.sources.iter().map(|span| span.to_string()).collect::<Vec<_>>();
+            parts.push(format!("also from {}", sources.join(", ")));
+        }
+        if !self.passes.is_empty() {
+            parts.push(format!("changed by {}", self.passes.join(", ")));
+        }
+        write!(f, "{}", parts.join("; "))
+    }
+}
@@ -14,7 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{Constant, Variable};
+use crate::{ops::covering_span, Constant, Variable};
+
+use conlang_span::Span;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// The [`Variable`] data type represents the product of a constant and variable in a linear combination.
 /// The following are all valid terms:
@@ -22,15 +27,35 @@ use crate::{Constant, Variable};
 /// - `0 * y`
 /// - `-1 * z`
 /// - `15 * x`
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Term {
+    /// The coefficient of the variable, which is one if it is omitted from the JSON.
+    #[serde(default = "Constant::one")]
     pub constant: Constant,
     pub variable: Variable,
+    #[serde(default, with = "conlang_span::span_json")]
+    pub span: Span,
 }
 
 impl Term {
+    /// Returns a new term, the product of `constant` and `variable`.
+    /// Its span covers those of the `constant` and the `variable`.
+    pub fn new(constant: Constant, variable: Variable) -> Self {
+        let span = covering_span([constant.span, variable.span]);
+        Self { constant, variable, span }
+    }
+
     /// Negates the term.
     pub fn negate(&mut self) {
         self.constant.negate();
     }
 }
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.constant.is_one() {
+            true => write!(f, "{}", self.variable),
+            false => write!(f, "{} * {}", self.constant, self.variable),
+        }
+    }
+}
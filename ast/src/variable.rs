@@ -14,17 +14,27 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use conlang_span::Span;
+use conlang_span::{Span, Symbol};
 
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// The [`Variable`] data type represents a variable in a linear combination.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Variable {
-    pub name: String,
+    pub name: Symbol,
+    #[serde(default, with = "conlang_span::span_json")]
     pub span: Span,
 }
 
+impl Variable {
+    /// Returns a new variable called `name`, without a source location.
+    /// The name is interned in the current session.
+    pub fn new(name: impl AsRef<str>) -> Self {
+        Self { name: Symbol::intern(name.as_ref()), span: Span::dummy() }
+    }
+}
+
 impl Display for Variable {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.name)
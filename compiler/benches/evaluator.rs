@@ -0,0 +1,75 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Measures the throughput of the evaluator on a generated circuit with a million constraints.
+
+use conlang_ast::{Assignment, Constant, Program, ProgramBuilder, Variable};
+use conlang_compiler::{EvaluationOptions, Evaluator, Values};
+use conlang_errors::emitter::Handler;
+use conlang_span::{session_globals::create_session_if_not_set_then, span::BytePos};
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use snarkvm_console::{network::Testnet3, types::Field};
+
+/// The number of constraints in the generated circuit.
+const CONSTRAINTS: usize = 1_000_000;
+
+/// Returns a circuit that squares `v0` repeatedly, `(v{i}) * (v{i}) = (v{i+1});`, and an assignment that satisfies it.
+fn squaring_chain(constraints: usize) -> (Program, Assignment) {
+    let mut builder = ProgramBuilder::new();
+    let mut inputs = Vec::with_capacity(constraints + 1);
+    let mut value = Field::<Testnet3>::from_u64(3);
+    inputs.push((Variable::new("v0"), Constant::from(value)));
+    for i in 0..constraints {
+        let (current, next) = (builder.var(format!("v{i}")), builder.var(format!("v{}", i + 1)));
+        builder.enforce(current.clone(), current, next);
+        value = value * value;
+        inputs.push((Variable::new(format!("v{}", i + 1)), Constant::from(value)));
+    }
+    (builder.build(), Assignment::new(inputs).unwrap())
+}
+
+fn bench_evaluator(c: &mut Criterion) {
+    create_session_if_not_set_then(|_| {
+        let (program, assignment) = squaring_chain(CONSTRAINTS);
+        let source = program.to_string();
+        let handler = Handler::default();
+
+        let mut group = c.benchmark_group("evaluator");
+        group.sample_size(10).throughput(Throughput::Elements(CONSTRAINTS as u64));
+
+        for short_circuit in [false, true] {
+            let options = EvaluationOptions { short_circuit, ..Default::default() };
+            let suffix = if short_circuit { "_short_circuit" } else { "" };
+
+            group.bench_function(format!("program{suffix}"), |b| {
+                b.iter(|| Evaluator::unsatisfied_constraints(&program, &assignment, &options).unwrap())
+            });
+            group.bench_function(format!("stream{suffix}"), |b| {
+                let values = Values::new(&assignment);
+                b.iter(|| {
+                    let constraints = conlang_parser::parse_constraints(&handler, &source, BytePos(0));
+                    Evaluator::evaluate(constraints, &values, &options).unwrap()
+                })
+            });
+        }
+
+        group.finish();
+    })
+}
+
+criterion_group!(benches, bench_evaluator);
+criterion_main!(benches);
@@ -17,12 +17,34 @@
 //! The compiler for Conlang programs.
 //!
 //! The [`Compiler`] type compiles Conlang programs into R1CS circuits.
-use conlang_ast::Program;
-use conlang_errors::{emitter::Handler, CompilerError, Result};
-use conlang_span::{session_globals::with_session_globals, source_map::FileName};
+use conlang_ast::{Assignment, Program};
+use conlang_errors::{emitter::Handler, CompilerError, ParserError, Result};
+use conlang_passes::{
+    CircuitStatistics,
+    Compose,
+    Linkage,
+    MutVisitor,
+    Pass,
+    PassContext,
+    PassManager,
+    PassReport,
+    ProgramPass,
+    SpanEraser,
+    StatisticsCollector,
+};
+use conlang_span::{session_globals::with_session_globals, source_map::FileName, Symbol};
 
-use crate::Evaluator;
-use std::{fs, path::PathBuf};
+use crate::{AstFormat, CompilerOptions, EvaluationOptions, Evaluator, OutputOptions, Values};
+use indexmap::IndexSet;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+/// Returns the path to the program of the imported package with the given name.
+/// It is given the name of the importing package, or `None` for an import of the compiled program.
+type ImportResolver<'a> = dyn Fn(Option<&str>, &str) -> Result<PathBuf> + 'a;
 
 /// The primary entry point of the Conlang compiler.
 #[derive(Clone)]
@@ -33,56 +55,353 @@ pub struct Compiler<'a> {
     path: &'a PathBuf,
     /// The AST for the program.
     program: Program,
+    /// The names of the variables of the program before the compiler stages, some of which optimizations can remove.
+    variable_names: IndexSet<Symbol>,
+    /// The directory the intermediate outputs are written to.
+    output_directory: PathBuf,
+    /// The intermediate outputs to write.
+    output_options: OutputOptions,
+    /// The options for the compiler stages.
+    compiler_options: CompilerOptions,
+    /// The options for evaluating inputs.
+    evaluation_options: EvaluationOptions,
+    /// The passes that can run in the compiler stages.
+    pass_manager: PassManager,
+    /// The names of the passes registered with [`Compiler::with_pass`].
+    custom_passes: Vec<String>,
+    /// The reports of the passes that ran.
+    pass_reports: Vec<PassReport>,
+    /// Returns the path to the program of an imported package.
+    import_resolver: Option<Rc<ImportResolver<'a>>>,
+    /// The mapping of the variables of each import of the program.
+    linkages: Vec<Linkage>,
+    /// The paths of the imported programs, in the order they were resolved.
+    import_paths: Vec<PathBuf>,
 }
 
 impl<'a> Compiler<'a> {
     /// Returns a new Conlang compiler.
     pub fn new(handler: &'a Handler, path: &'a PathBuf) -> Self {
-        Self { handler, path, program: Program::default() }
+        Self {
+            handler,
+            path,
+            program: Program::default(),
+            variable_names: IndexSet::new(),
+            output_directory: PathBuf::default(),
+            output_options: OutputOptions::default(),
+            compiler_options: CompilerOptions::default(),
+            evaluation_options: EvaluationOptions::default(),
+            pass_manager: PassManager::new(),
+            custom_passes: Vec::new(),
+            pass_reports: Vec::new(),
+            import_resolver: None,
+            linkages: Vec::new(),
+            import_paths: Vec::new(),
+        }
+    }
+
+    /// Registers the custom `pass`, which runs after the built-in passes unless the options name a pipeline.
+    /// A pass with the name of a built-in pass replaces it.
+    pub fn with_pass(mut self, pass: impl ProgramPass + 'static) -> Self {
+        if !self.pass_manager.names().any(|name| name == pass.name()) {
+            self.custom_passes.push(pass.name().to_string());
+        }
+        self.pass_manager.register(pass);
+        self
+    }
+
+    /// Configures the compiler stages with `options`.
+    pub fn with_compiler_options(mut self, options: CompilerOptions) -> Self {
+        self.compiler_options = options;
+        self
+    }
+
+    /// Configures the evaluation of inputs with `options`.
+    pub fn with_evaluation_options(mut self, options: EvaluationOptions) -> Self {
+        self.evaluation_options = options;
+        self
+    }
+
+    /// Configures the compiler to write the intermediate outputs selected by `options` into `output_directory`.
+    pub fn with_output_options(mut self, output_directory: PathBuf, options: OutputOptions) -> Self {
+        self.output_directory = output_directory;
+        self.output_options = options;
+        self
+    }
+
+    /// Configures the compiler to resolve imports into the path of the imported program with `resolver`,
+    /// e.g., with `Package::resolve_import` of the `conlang-package` crate.
+    pub fn with_import_resolver(mut self, resolver: impl Fn(Option<&str>, &str) -> Result<PathBuf> + 'a) -> Self {
+        self.import_resolver = Some(Rc::new(resolver));
+        self
+    }
+
+    /// Returns the mapping of the variables of each import of the program into the variables of the program.
+    pub fn linkages(&self) -> &[Linkage] {
+        &self.linkages
+    }
+
+    /// Returns the paths of the programs that the program imports, directly or through its imports.
+    /// A path is included once it is resolved, so that it is known even if the imported program fails to parse.
+    pub fn import_paths(&self) -> &[PathBuf] {
+        &self.import_paths
+    }
+
+    /// Returns the AST of the program.
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Returns the names of the variables of the program, including those that optimizations removed.
+    pub fn variable_names(&self) -> &IndexSet<Symbol> {
+        &self.variable_names
     }
 
     /// Parses and stores a program file content from a string, constructs a syntax tree, and generates a program.
     pub fn parse_program_from_string(&mut self, program_string: &str, name: FileName) -> Result<()> {
+        let _span = tracing::debug_span!("parse", file = %name).entered();
+
         // Register the source (`program_string`) in the source map.
         let prg_sf = with_session_globals(|s| s.source_map.new_source(program_string, name));
 
         // Use the parser to construct the abstract syntax tree (ast).
-        self.program = conlang_parser::parse_program(self.handler, &prg_sf.src, prg_sf.start_pos)?;
+        let program = conlang_parser::parse_program(self.handler, &prg_sf.src, prg_sf.start_pos)?;
 
         // Emit any errors from the handler.
         self.handler.last_err().map_err(|e| *e)?;
 
+        self.program = self.link_imports(program, &mut Vec::new())?;
+        self.variable_names = self.program.variable_names();
+
+        self.dump_ast(&self.program, "parsing");
+        if self.output_options.initial_ast {
+            self.write_ast(&self.program, "ast")?;
+        }
+        Ok(())
+    }
+
+    /// Loads and stores a program from its JSON-serialized AST, e.g., as written by `--emit`.
+    /// The spans of the AST are discarded, since they refer to a source that is not in the source map.
+    pub fn parse_program_from_json(&mut self, json: &str) -> Result<()> {
+        let _span = tracing::debug_span!("load", file = %self.path.display()).entered();
+
+        let mut program = Program::from_json_string(json)?;
+        SpanEraser.visit_program_mut(&mut program);
+        self.program = self.link_imports(program, &mut Vec::new())?;
+        self.variable_names = self.program.variable_names();
+
+        self.dump_ast(&self.program, "loading");
+        if self.output_options.initial_ast {
+            self.write_ast(&self.program, "ast")?;
+        }
         Ok(())
     }
 
     /// Parses and stores the main program file, constructs a syntax tree, and generates a program.
+    /// A `.json` file is loaded as a serialized AST instead.
     pub fn parse_program(&mut self) -> Result<()> {
         // Load the program file.
         let program_string = fs::read_to_string(self.path).map_err(|e| CompilerError::file_read_error(self.path, e))?;
 
-        self.parse_program_from_string(&program_string, FileName::Real(self.path.clone()))
+        match is_json(self.path) {
+            true => self.parse_program_from_json(&program_string),
+            false => self.parse_program_from_string(&program_string, FileName::Real(self.path.clone())),
+        }
+    }
+
+    /// Returns the `program` with its imports composed into it, after linking their own imports.
+    /// The `importers` are the packages whose imports are being linked, which must not be imported again.
+    fn link_imports(&mut self, mut program: Program, importers: &mut Vec<String>) -> Result<Program> {
+        for import in std::mem::take(&mut program.imports) {
+            if importers.contains(&import.package) {
+                let cycle = importers.iter().chain([&import.package]).map(|package| format!("`{package}`"));
+                return Err(CompilerError::import_cycle(cycle.collect::<Vec<_>>().join(" -> "), import.span).into());
+            }
+
+            let resolver = self.import_resolver.clone();
+            let resolver = resolver.ok_or_else(|| CompilerError::unresolved_import(&import.package, import.span))?;
+            let path = resolver(importers.last().map(String::as_str), &import.package)?;
+            self.import_paths.push(path.clone());
+            let _span = tracing::debug_span!("import", package = %import.package, file = %path.display()).entered();
+
+            // Parse the imported program, registering its source in the source map.
+            let source = fs::read_to_string(&path).map_err(|e| CompilerError::file_read_error(&path, e))?;
+            let source_file = with_session_globals(|s| s.source_map.new_source(&source, FileName::Real(path.clone())));
+            let subprogram = conlang_parser::parse_program(self.handler, &source_file.src, source_file.start_pos)?;
+            self.handler.last_err().map_err(|e| *e)?;
+
+            importers.push(import.package.clone());
+            let subprogram = self.link_imports(subprogram, importers)?;
+            importers.pop();
+
+            let linkage = program.compose(subprogram, &import)?;
+            // Only the imports of the compiled program are named in its variables.
+            if importers.is_empty() {
+                self.linkages.push(linkage);
+            }
+        }
+        Ok(program)
     }
 
     /// Parses the input file and checks that the assignment satisfies the program.
+    /// A `.json` file is loaded as a JSON object that maps variables to values, e.g., `{"x": "2"}`.
     pub fn evaluate_input(&mut self, input_path: &PathBuf) -> Result<bool> {
+        let _span = tracing::debug_span!("evaluate", file = %input_path.display()).entered();
+
+        let assignment = self.parse_input(input_path)?;
+
+        // Warn about input variables that the program does not use.
+        Evaluator::check_unused_inputs(self.handler, &self.variable_names, &assignment);
+
+        // Check that the assignment satisfies the program.
+        Evaluator::check_assignment(self.handler, &self.program, &assignment, &self.evaluation_options)
+    }
+
+    /// Parses the input file and checks that the assignment satisfies the program, evaluating the constraints
+    /// of the program while it is parsed. The program is neither stored nor optimized, and cannot have imports.
+    /// Since the program is not known as a whole, unused inputs are not reported.
+    /// A `.json` program is loaded as a whole, since it is a single JSON value.
+    pub fn stream_input(&mut self, input_path: &PathBuf) -> Result<bool> {
+        let _span = tracing::debug_span!("stream", file = %input_path.display()).entered();
+
+        let values = Values::new(&self.parse_input(input_path)?);
+
+        // Load the program file.
+        let program_string = fs::read_to_string(self.path).map_err(|e| CompilerError::file_read_error(self.path, e))?;
+        if is_json(self.path) {
+            let program = Program::from_json_string(&program_string)?;
+            if let Some(import) = program.imports.into_iter().next() {
+                return Err(ParserError::import_in_stream(import.package, import.span).into());
+            }
+            let constraints = program.constraints.into_iter().map(Ok);
+            return Evaluator::check_constraints(self.handler, constraints, &values, &self.evaluation_options);
+        }
+
+        // Register the source in the source map, and evaluate its constraints as they are parsed.
+        let prg_sf =
+            with_session_globals(|s| s.source_map.new_source(&program_string, FileName::Real(self.path.clone())));
+        let constraints = conlang_parser::parse_constraints(self.handler, &prg_sf.src, prg_sf.start_pos);
+        Evaluator::check_constraints(self.handler, constraints, &values, &self.evaluation_options)
+    }
+
+    /// Parses the input file into an assignment.
+    /// A `.json` file is loaded as a JSON object that maps variables to values, e.g., `{"x": "2"}`.
+    fn parse_input(&self, input_path: &PathBuf) -> Result<Assignment> {
         // Load the input file.
         let input_string = fs::read_to_string(input_path).map_err(|e| CompilerError::file_read_error(input_path, e))?;
 
-        // Register the source (`input_string`) in the source map.
-        let input_sf =
-            with_session_globals(|s| s.source_map.new_source(&input_string, FileName::Real(input_path.clone())));
+        let assignment = match is_json(input_path) {
+            true => Assignment::from_json_string(&input_string)?,
+            false => {
+                // Register the source (`input_string`) in the source map.
+                let input_sf = with_session_globals(|s| {
+                    s.source_map.new_source(&input_string, FileName::Real(input_path.clone()))
+                });
 
-        let assignment = conlang_parser::parse_input(self.handler, &input_sf.src, input_sf.start_pos)?;
+                let assignment = conlang_parser::parse_input(self.handler, &input_sf.src, input_sf.start_pos)?;
 
-        // Emit any errors from the handler.
-        self.handler.last_err().map_err(|e| *e)?;
+                // Emit any errors from the handler.
+                self.handler.last_err().map_err(|e| *e)?;
+                assignment
+            }
+        };
+        Ok(assignment)
+    }
 
-        // Check that the assignment satisfies the program.
-        Ok(Evaluator::check_assignment(&self.program, &assignment))
+    /// Runs the statistics pass, measuring the size of the circuit.
+    pub fn statistics_pass(&self) -> CircuitStatistics {
+        StatisticsCollector::do_pass(&self.program)
     }
 
-    /// Runs the compiler stages.
+    /// Returns the names of the passes to run: the pipeline of the options, without the skipped passes.
+    /// Without a pipeline in the options, the built-in passes of the optimization level run, followed by the passes
+    /// registered with [`Compiler::with_pass`].
+    pub fn pipeline(&self) -> Result<Vec<String>> {
+        let mut pipeline = match &self.compiler_options.passes {
+            Some(passes) => passes.clone(),
+            None => self
+                .compiler_options
+                .default_passes()
+                .into_iter()
+                .map(str::to_string)
+                .chain(self.custom_passes.iter().cloned())
+                .collect(),
+        };
+
+        for name in self.compiler_options.skipped_passes.iter() {
+            if !self.pass_manager.names().any(|registered| registered == name) {
+                let available = self.pass_manager.names().collect::<Vec<_>>().join(", ");
+                return Err(CompilerError::unknown_pass(name, available).into());
+            }
+        }
+        pipeline.retain(|name| !self.compiler_options.skipped_passes.contains(name));
+        Ok(pipeline)
+    }
+
+    /// Returns the reports of the passes that ran in the compiler stages.
+    pub fn pass_reports(&self) -> &[PassReport] {
+        &self.pass_reports
+    }
+
+    /// Runs the compiler stages, i.e., the passes of the pipeline.
     pub fn compiler_stages(&mut self) -> Result<()> {
+        let _span = tracing::debug_span!("compiler stages").entered();
+
+        let pipeline = self.pipeline()?;
+        if self.output_options.normalized_ast && !pipeline.iter().any(|name| name == "normalize") {
+            return Err(CompilerError::stage_not_run("normalized", "normalize").into());
+        }
+        let program = std::mem::take(&mut self.program);
+        let context = PassContext { handler: self.handler, public_variables: &self.compiler_options.public_variables };
+
+        let (program, reports) = self.pass_manager.run(&pipeline, program, &context, |pass, program| {
+            self.dump_ast(program, pass.name());
+            if pass.name() == "normalize" && self.output_options.normalized_ast {
+                self.write_ast(program, "normalized")?;
+            }
+            Ok(())
+        })?;
+
+        for report in reports.iter() {
+            tracing::debug!(
+                "Pass `{}` took {:.2?}, leaving {} constraints, with {} error(s) and {} warning(s).",
+                report.name,
+                report.duration,
+                report.constraints,
+                report.errors,
+                report.warnings
+            );
+        }
+        self.program = program;
+        self.pass_reports = reports;
+
+        if self.output_options.optimized_ast {
+            self.write_ast(&self.program, "optimized")?;
+        }
+        Ok(())
+    }
+
+    /// Prints the `program` at the debug level, so that the effect of each stage can be inspected.
+    fn dump_ast(&self, program: &Program, stage: &str) {
+        tracing::debug!("Program after {stage}:\n{program:#}");
+    }
+
+    /// Writes the `program` into the output directory, in a file named after the program and the `stage`.
+    fn write_ast(&self, program: &Program, stage: &str) -> Result<()> {
+        fs::create_dir_all(&self.output_directory)
+            .map_err(|e| CompilerError::file_write_error(&self.output_directory, e))?;
+
+        let name = self.path.file_stem().unwrap_or_default().to_string_lossy();
+        let file_name = format!("{name}.{stage}.{}", self.output_options.ast_format.extension());
+        match self.output_options.ast_format {
+            AstFormat::Json => program.to_json_file(self.output_directory.clone(), &file_name)?,
+            AstFormat::Conlang => {
+                let path = self.output_directory.join(&file_name);
+                fs::write(&path, format!("{program:#}")).map_err(|e| CompilerError::file_write_error(&path, e))?;
+            }
+        }
+
+        tracing::debug!("Wrote the {stage} AST to `{}`", self.output_directory.join(file_name).display());
         Ok(())
     }
 
@@ -95,3 +414,55 @@ impl<'a> Compiler<'a> {
         Ok(())
     }
 }
+
+/// Returns whether the file at `path` is a JSON file.
+fn is_json(path: &Path) -> bool {
+    matches!(path.extension(), Some(extension) if extension == "json")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Compiler;
+    use conlang_ast::{Import, Program};
+    use conlang_errors::emitter::Handler;
+    use conlang_span::session_globals::create_session_if_not_set_then;
+    use std::fs;
+
+    #[test]
+    fn test_folded_inputs_are_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let (program_path, input_path) = (dir.path().join("main.conlang"), dir.path().join("main.in"));
+        fs::write(&program_path, "(x) * (0) = (0);\n(0) * (y) = (z);\n").unwrap();
+        fs::write(&input_path, "x = 1;\ny = 2;\nz = 0;\nw = 3;\n").unwrap();
+
+        create_session_if_not_set_then(|_| {
+            let (handler, buffer) = Handler::new_with_buf();
+            let mut compiler = Compiler::new(&handler, &program_path);
+            compiler.compile().unwrap();
+            assert_eq!(compiler.program().to_string(), "(z) * (1) = (0);\n");
+
+            // The variables that folding removed are still inputs of the program.
+            assert!(compiler.evaluate_input(&input_path).unwrap());
+            let warnings: Vec<_> =
+                buffer.extract_warnings().into_inner().iter().map(|warning| warning.message().to_string()).collect();
+            assert_eq!(warnings, ["The input variable `w` is not used by the program."]);
+        });
+    }
+
+    #[test]
+    fn test_stream_json_program_with_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let (program_path, input_path) = (dir.path().join("main.json"), dir.path().join("main.in"));
+        fs::write(&input_path, "x = 1;\n").unwrap();
+
+        create_session_if_not_set_then(|_| {
+            let program = Program { imports: vec![Import::new("sha256", "sha")], ..Default::default() };
+            fs::write(&program_path, program.to_json_string().unwrap()).unwrap();
+
+            // The imports of a JSON program are rejected, as in a streamed `.conlang` program.
+            let handler = Handler::default();
+            let err = Compiler::new(&handler, &program_path).stream_input(&input_path).unwrap_err();
+            assert!(err.to_string().contains("sha256"), "{err}");
+        });
+    }
+}
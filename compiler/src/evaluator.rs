@@ -14,41 +14,330 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use conlang_ast::{Assignment, Constraint, LinearCombination, Program, Term};
-use conlang_errors::{CompilerError, Result};
+use crate::EvaluationOptions;
 
+use conlang_ast::{Assignment, Constant, Constraint, LinearCombination, Program, Term, Variable};
+use conlang_errors::{emitter::Handler, CompilerError, CompilerWarning, ConlangError, Result};
+use conlang_span::{edit_distance::find_best_match_for_name, Span, Symbol};
+
+use indexmap::IndexSet;
+use rayon::prelude::*;
 use snarkvm_console::{network::Testnet3, types::Field};
+use std::borrow::Borrow;
 
 pub struct Evaluator;
 
+/// The values of an assignment, stored densely by the index of each variable's [`Symbol`],
+/// so that evaluating a constraint does not hash any names.
+#[derive(Clone, Debug, Default)]
+pub struct Values {
+    /// The value of each variable, at the index of its symbol.
+    values: Vec<Option<Field<Testnet3>>>,
+    /// The assigned variables, in order, to suggest a name from when a variable is not assigned.
+    names: Vec<Symbol>,
+}
+
+impl Values {
+    /// Returns the values of the variables in the `assignment`.
+    pub fn new(assignment: &Assignment) -> Self {
+        let mut values = Self { values: Vec::new(), names: Vec::with_capacity(assignment.map.len()) };
+        for (name, constant) in assignment.map.iter() {
+            values.insert(*name, constant.value);
+        }
+        values
+    }
+
+    /// Returns the value of the variable `name`, if it is assigned.
+    pub fn get(&self, name: Symbol) -> Option<Field<Testnet3>> {
+        self.values.get(name.index()).copied().flatten()
+    }
+
+    /// Assigns the `value` to the variable `name`.
+    pub fn insert(&mut self, name: Symbol, value: Field<Testnet3>) {
+        if self.values.len() <= name.index() {
+            self.values.resize(name.index() + 1, None);
+        }
+        if self.values[name.index()].replace(value).is_none() {
+            self.names.push(name);
+        }
+    }
+
+    /// Returns the assigned variables, in the order they were assigned.
+    pub fn names(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.names.iter().copied()
+    }
+}
+
+/// A constraint that an assignment does not satisfy.
+/// It keeps the spans of the constraint, so that it can be reported after a streamed constraint is dropped.
+#[derive(Clone, Debug)]
+pub struct UnsatisfiedConstraint {
+    /// The index of the constraint in the program.
+    pub index: usize,
+    /// The values of `A`, `B` and `C` under the assignment.
+    pub values: [Field<Testnet3>; 3],
+    /// The spans of `A`, `B` and `C`.
+    pub spans: [Span; 3],
+    /// The span of the constraint.
+    pub span: Span,
+}
+
+impl UnsatisfiedConstraint {
+    /// Returns the unsatisfied `constraint` at `index`, where `A`, `B` and `C` evaluate to the `values`.
+    fn new(index: usize, constraint: &Constraint, values: [Field<Testnet3>; 3]) -> Self {
+        let Constraint { a, b, c, span, .. } = constraint;
+        Self { index, values, spans: [a.span, b.span, c.span], span: *span }
+    }
+
+    /// Returns the error describing why the constraint is not satisfied.
+    pub fn to_error(&self) -> CompilerError {
+        let [a_value, b_value, c_value] = self.values;
+        let [a_span, b_span, c_span] = self.spans;
+        CompilerError::constraint_not_satisfied(a_value, a_span, b_value, b_span, c_value, c_span, self.span)
+    }
+}
+
 impl Evaluator {
-    pub fn check_assignment(program: &Program, assignment: &Assignment) -> bool {
-        let mut is_satisfied = true;
-        for Constraint { a, b, c } in program.constraints.iter() {
-            let a = Self::evaluate_linear_combination(a, assignment).unwrap();
-            let b = Self::evaluate_linear_combination(b, assignment).unwrap();
-            let c = Self::evaluate_linear_combination(c, assignment).unwrap();
-            if a * b != c {
-                is_satisfied = false;
+    /// Checks that the `assignment` satisfies every constraint in the `program`.
+    /// An error is emitted through the `handler` for each unsatisfied constraint.
+    pub fn check_assignment(
+        handler: &Handler,
+        program: &Program,
+        assignment: &Assignment,
+        options: &EvaluationOptions,
+    ) -> Result<bool> {
+        let unsatisfied = Self::unsatisfied_constraints(program, assignment, options)?;
+        for constraint in unsatisfied.iter() {
+            handler.emit_err(constraint.to_error());
+        }
+        Ok(unsatisfied.is_empty())
+    }
+
+    /// Checks that the `values` satisfy the streamed `constraints`, e.g., from `conlang_parser::parse_constraints`.
+    /// An error is emitted through the `handler` for each unsatisfied constraint.
+    pub fn check_constraints(
+        handler: &Handler,
+        constraints: impl IntoIterator<Item = Result<Constraint>>,
+        values: &Values,
+        options: &EvaluationOptions,
+    ) -> Result<bool> {
+        let unsatisfied = Self::evaluate(constraints, values, options)?;
+        for constraint in unsatisfied.iter() {
+            handler.emit_err(constraint.to_error());
+        }
+        Ok(unsatisfied.is_empty())
+    }
+
+    /// Returns the constraints in the `program` that the `assignment` does not satisfy.
+    /// This function does not emit errors, so it can be called from multiple threads.
+    ///
+    /// For example, with a program built by a [`ProgramBuilder`](conlang_ast::ProgramBuilder):
+    /// ```rust
+    /// use conlang_ast::{Assignment, Constant, ProgramBuilder};
+    /// use conlang_compiler::{EvaluationOptions, Evaluator};
+    ///
+    /// # conlang_span::session_globals::create_session_if_not_set_then(|_| {
+    /// let mut builder = ProgramBuilder::new();
+    /// let (x, y) = (builder.var("x"), builder.var("y"));
+    /// builder.enforce(x.clone(), x.clone(), y.clone());
+    /// let program = builder.build();
+    ///
+    /// let assignment = Assignment::new(vec![(x.variable, Constant::from(3)), (y.variable, Constant::from(9))]).unwrap();
+    /// let options = EvaluationOptions::default();
+    /// assert!(Evaluator::unsatisfied_constraints(&program, &assignment, &options).unwrap().is_empty());
+    /// # });
+    /// ```
+    pub fn unsatisfied_constraints(
+        program: &Program,
+        assignment: &Assignment,
+        options: &EvaluationOptions,
+    ) -> Result<Vec<UnsatisfiedConstraint>> {
+        let values = Self::recover_eliminated_variables(program, assignment)?;
+        Self::evaluate(program.constraints.iter().map(Ok), &values, options)
+    }
+
+    /// Returns the `constraints` that the `values` do not satisfy, in order.
+    /// With [`EvaluationOptions::short_circuit`], only the first of them is returned.
+    ///
+    /// The constraints are taken from the iterator one chunk at a time, and each chunk is evaluated in parallel,
+    /// so that a program can be evaluated while it is parsed without holding all of its constraints.
+    /// This function errors on the first error of the iterator, or the first variable that is not assigned,
+    /// unless it short-circuits on an unsatisfied constraint before it.
+    /// ```rust
+    /// use conlang_ast::Assignment;
+    /// use conlang_compiler::{EvaluationOptions, Evaluator, Values};
+    /// use conlang_errors::emitter::Handler;
+    /// use conlang_span::span::BytePos;
+    ///
+    /// # conlang_span::session_globals::create_session_if_not_set_then(|_| {
+    /// let handler = Handler::default();
+    /// let source = "(x) * (x) = (y);\n(y) * (1) = (x);\n(x) * (1) = (y);\n";
+    /// let values = Values::new(&Assignment::from_json_string(r#"{"x": "2", "y": "4"}"#).unwrap());
+    ///
+    /// let options = EvaluationOptions { short_circuit: true, ..Default::default() };
+    /// let constraints = conlang_parser::parse_constraints(&handler, source, BytePos(0));
+    /// let unsatisfied = Evaluator::evaluate(constraints, &values, &options).unwrap();
+    /// assert_eq!(unsatisfied.iter().map(|constraint| constraint.index).collect::<Vec<_>>(), [1]);
+    /// # });
+    /// ```
+    pub fn evaluate<C: Borrow<Constraint> + Sync>(
+        constraints: impl IntoIterator<Item = Result<C>>,
+        values: &Values,
+        options: &EvaluationOptions,
+    ) -> Result<Vec<UnsatisfiedConstraint>> {
+        let mut constraints = constraints.into_iter();
+        let mut unsatisfied = Vec::new();
+        let mut offset = 0;
+        loop {
+            // The constraints before an error of the iterator are still evaluated,
+            // so that the outcome does not depend on the chunk size.
+            let mut chunk = Vec::new();
+            let mut error = None;
+            for constraint in constraints.by_ref().take(options.chunk_size.max(1)) {
+                match constraint {
+                    Ok(constraint) => chunk.push(constraint),
+                    Err(err) => {
+                        error = Some(err);
+                        break;
+                    }
+                }
             }
+            if chunk.is_empty() && error.is_none() {
+                return Ok(unsatisfied);
+            }
+
+            // The workers only look up values by symbol, so they do not need the session to resolve names.
+            let check = |(index, constraint)| {
+                let outcome = Self::evaluate_constraint(constraint, values).transpose()?;
+                Some((offset + index, constraint, outcome))
+            };
+            let constraints = chunk.par_iter().map(Borrow::borrow).enumerate();
+            let failures: Vec<_> = match options.short_circuit {
+                true => constraints.find_map_first(check).into_iter().collect(),
+                false => constraints.filter_map(check).collect(),
+            };
+
+            for (index, constraint, outcome) in failures {
+                match outcome {
+                    Ok(values) => unsatisfied.push(UnsatisfiedConstraint::new(index, constraint, values)),
+                    Err(variable) => return Err(Self::not_assigned(variable, values)),
+                }
+            }
+            if options.short_circuit && !unsatisfied.is_empty() {
+                return Ok(unsatisfied);
+            }
+            if let Some(error) = error {
+                return Err(error);
+            }
+            offset += chunk.len();
         }
-        is_satisfied
     }
 
-    fn evaluate_linear_combination(
+    /// Returns the values of `A`, `B` and `C` if the `values` do not satisfy the `constraint`,
+    /// or the first variable of the constraint that is not assigned.
+    fn evaluate_constraint<'a>(
+        constraint: &'a Constraint,
+        values: &Values,
+    ) -> Result<Option<[Field<Testnet3>; 3]>, &'a Variable> {
+        let a_value = Self::try_evaluate_linear_combination(&constraint.a, values)?;
+        let b_value = Self::try_evaluate_linear_combination(&constraint.b, values)?;
+        let c_value = Self::try_evaluate_linear_combination(&constraint.c, values)?;
+        Ok((a_value * b_value != c_value).then_some([a_value, b_value, c_value]))
+    }
+
+    /// Emits a warning through the `handler` for each variable in the `assignment` that is not one of the
+    /// `variable_names` of the program, e.g., as returned by [`Program::variable_names`].
+    pub fn check_unused_inputs(handler: &Handler, variable_names: &IndexSet<Symbol>, assignment: &Assignment) {
+        for (name, span) in assignment.spans.iter() {
+            if !variable_names.contains(name) {
+                let suggestion =
+                    find_best_match_for_name(variable_names.iter().map(|name| name.as_str()), &name.as_str(), None);
+                handler.emit_warning(CompilerWarning::unused_input_variable(name, suggestion, *span));
+            }
+        }
+    }
+
+    /// Returns the values of the `assignment`, extended with the values of the variables eliminated from the `program`.
+    /// This function errors if the assignment gives an eliminated variable a different value.
+    pub fn recover_eliminated_variables(program: &Program, assignment: &Assignment) -> Result<Values> {
+        let mut values = Values::new(assignment);
+        for eliminated in program.eliminated.iter() {
+            let name = eliminated.variable.name;
+            let expected = Self::evaluate_linear_combination(&eliminated.value, &values)?;
+            match assignment.map.get(&name) {
+                Some(value) if value.value != expected => {
+                    let span = assignment.spans.get(&name).copied().unwrap_or_default();
+                    return Err(CompilerError::eliminated_variable_mismatch(
+                        &eliminated.variable,
+                        value,
+                        Constant::from(expected),
+                        span,
+                    )
+                    .into());
+                }
+                Some(_) => {}
+                None => values.insert(name, expected),
+            }
+        }
+        Ok(values)
+    }
+
+    /// Evaluates the `linear_combination` under the `values`.
+    /// This function errors if a variable in the linear combination is not assigned.
+    pub fn evaluate_linear_combination(
         linear_combination: &LinearCombination,
-        assignment: &Assignment,
+        values: &Values,
     ) -> Result<Field<Testnet3>> {
+        Self::try_evaluate_linear_combination(linear_combination, values)
+            .map_err(|variable| Self::not_assigned(variable, values))
+    }
+
+    /// Evaluates the `linear_combination` under the `values`, or returns its first variable that is not assigned.
+    fn try_evaluate_linear_combination<'a>(
+        linear_combination: &'a LinearCombination,
+        values: &Values,
+    ) -> Result<Field<Testnet3>, &'a Variable> {
         let mut result = linear_combination.constant.value;
+        for Term { variable, constant, .. } in linear_combination.terms.iter() {
+            result += values.get(variable.name).ok_or(variable)? * constant.value;
+        }
+        Ok(result)
+    }
 
-        for Term { variable, constant } in linear_combination.terms.iter() {
-            let value = match assignment.map.get(&variable.name) {
-                Some(value) => value,
-                None => return Err(CompilerError::variable_not_assigned(variable).into()),
+    /// Returns the error for the `variable`, which the `values` do not assign.
+    fn not_assigned(variable: &Variable, values: &Values) -> ConlangError {
+        // Suggest an assigned variable with a similar name, if there is one.
+        let suggestion = find_best_match_for_name(values.names().map(Symbol::as_str), &variable.name.as_str(), None);
+        CompilerError::variable_not_assigned(variable, suggestion, variable.span).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EvaluationOptions, Evaluator, Values};
+    use conlang_ast::Assignment;
+    use conlang_errors::emitter::Handler;
+    use conlang_span::{session_globals::create_session_if_not_set_then, span::BytePos};
+
+    #[test]
+    fn test_evaluate_chunks() {
+        create_session_if_not_set_then(|_| {
+            let source = "(x) * (x) = (y);\n(y) * (1) = (x);\n(x) * = (y);\n(y) * (1) = (y);\n(x) * (1) = (y);\n";
+            let values = Values::new(&Assignment::from_json_string(r#"{"x": "2", "y": "4"}"#).unwrap());
+            let unsatisfied = |short_circuit, chunk_size| {
+                let handler = Handler::default();
+                let constraints = conlang_parser::parse_constraints(&handler, source, BytePos(0));
+                let options = EvaluationOptions { short_circuit, chunk_size };
+                Evaluator::evaluate(constraints, &values, &options)
+                    .map(|unsatisfied| unsatisfied.iter().map(|constraint| constraint.index).collect::<Vec<_>>())
+                    .map_err(|err| err.to_string())
             };
-            result += value.value * constant.value;
-        }
 
-        Ok(result)
+            // The first failure is found for every chunk size, even if a later constraint does not parse.
+            for chunk_size in [1, 2, 1024] {
+                assert_eq!(unsatisfied(true, chunk_size), Ok(vec![1]));
+                assert!(unsatisfied(false, chunk_size).is_err());
+            }
+        });
     }
 }
@@ -24,3 +24,6 @@ pub use compiler::*;
 
 mod evaluator;
 pub use evaluator::*;
+
+mod options;
+pub use options::*;
@@ -0,0 +1,116 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fmt, str::FromStr};
+
+/// The format in which the compiler writes intermediate ASTs.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AstFormat {
+    /// The serialized AST, including spans.
+    #[default]
+    Json,
+    /// The pretty-printed conlang program.
+    Conlang,
+}
+
+impl AstFormat {
+    /// Returns the file extension for the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Conlang => "conlang",
+        }
+    }
+}
+
+impl FromStr for AstFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "conlang" => Ok(Self::Conlang),
+            _ => Err(format!("unknown AST format `{s}`, expected `json` or `conlang`")),
+        }
+    }
+}
+
+impl fmt::Display for AstFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+/// Options for the intermediate outputs of the compiler.
+#[derive(Clone, Debug, Default)]
+pub struct OutputOptions {
+    /// If enabled, writes the AST after parsing.
+    pub initial_ast: bool,
+    /// If enabled, writes the AST after normalization.
+    pub normalized_ast: bool,
+    /// If enabled, writes the AST after optimization.
+    pub optimized_ast: bool,
+    /// The format in which the ASTs are written.
+    pub ast_format: AstFormat,
+}
+
+/// Options for the compiler stages.
+#[derive(Clone, Debug)]
+pub struct CompilerOptions {
+    /// The optimization level. At `0`, the program is not optimized.
+    /// At `1`, constants are folded, and equivalent constraints are removed.
+    /// At `2`, linear constraints are used to eliminate variables as well.
+    pub optimization_level: u8,
+    /// The public variables, which optimizations must keep in the program.
+    pub public_variables: Vec<String>,
+    /// The names of the passes to run, instead of those of the optimization level.
+    pub passes: Option<Vec<String>>,
+    /// The names of the passes not to run.
+    pub skipped_passes: Vec<String>,
+}
+
+impl CompilerOptions {
+    /// Returns the names of the built-in passes that run at the optimization level.
+    pub fn default_passes(&self) -> Vec<&'static str> {
+        match self.optimization_level {
+            0 => vec!["normalize"],
+            1 => vec!["normalize", "fold", "dedup"],
+            _ => vec!["normalize", "eliminate", "fold", "dedup"],
+        }
+    }
+}
+
+impl Default for CompilerOptions {
+    fn default() -> Self {
+        Self { optimization_level: 1, public_variables: Vec::new(), passes: None, skipped_passes: Vec::new() }
+    }
+}
+
+/// Options for evaluating assignments against a program.
+#[derive(Clone, Debug)]
+pub struct EvaluationOptions {
+    /// If enabled, the evaluation stops at the first unsatisfied constraint.
+    pub short_circuit: bool,
+    /// The number of constraints that are evaluated in parallel at a time.
+    /// When the constraints are streamed, this bounds how many of them are held in memory.
+    pub chunk_size: usize,
+}
+
+impl Default for EvaluationOptions {
+    fn default() -> Self {
+        Self { short_circuit: false, chunk_size: 1 << 14 }
+    }
+}
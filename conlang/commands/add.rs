@@ -0,0 +1,69 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+
+use conlang_errors::{PackageError, Result};
+use conlang_package::Package;
+
+use clap::StructOpt;
+use semver::VersionReq;
+
+/// Add a dependency from the package registry
+#[derive(StructOpt, Debug)]
+pub struct Add {
+    #[structopt(name = "NAME", help = "The name of the package, optionally followed by `@<version requirement>`.")]
+    name: String,
+}
+
+impl Command for Add {
+    type Input = Package;
+    type Output = ();
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        context.open_package()
+    }
+
+    fn apply(self, context: Context, mut package: Self::Input) -> Result<Self::Output> {
+        let registry = context.registry()?;
+
+        // Without a requirement, depend on the latest version that is compatible with the published one.
+        let (name, requirement) = match self.name.split_once('@') {
+            Some((name, requirement)) => (name, requirement.to_string()),
+            None => {
+                let versions = registry.versions(&self.name)?;
+                let latest = versions.last().ok_or_else(|| PackageError::no_matching_version(&self.name, "*"))?;
+                (self.name.as_str(), latest.to_string())
+            }
+        };
+
+        // Check that the requirement can be met before changing the manifest.
+        let version_requirement =
+            VersionReq::parse(&requirement).map_err(|e| PackageError::invalid_version_requirement(&requirement, e))?;
+        registry.resolve(name, &version_requirement)?;
+
+        // Install the dependencies first, so that the manifest is only changed if they can be installed.
+        package.manifest.dependencies.insert(name.to_string(), requirement);
+        let lockfile = package.install_dependencies(&registry)?;
+        package.manifest.write_to_dir(&package.root)?;
+
+        if let Some(locked) = lockfile.get(name) {
+            tracing::info!("Added `{name}` v{}.", locked.version);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    commands::{BuildOptions, Command},
+    context::Context,
+};
+
+use conlang_compiler::{AstFormat, Compiler, OutputOptions};
+use conlang_errors::{emitter::Handler, Result};
+
+use clap::StructOpt;
+use std::path::PathBuf;
+
+/// Compile the program of a project
+#[derive(StructOpt, Debug)]
+pub struct Build {
+    #[structopt(name = "PATH", help = "The path to the `.conlang` file. Defaults to the entry file of the project.")]
+    program_path: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        use_value_delimiter = true,
+        possible_values = &["ast", "normalized", "optimized"],
+        help = "Writes the AST after the given compiler stages, e.g. `--emit ast,normalized,optimized`."
+    )]
+    emit: Vec<String>,
+
+    #[structopt(long, default_value = "json", possible_values = &["json", "conlang"], help = "The format of the emitted ASTs.")]
+    emit_format: AstFormat,
+
+    #[structopt(
+        long,
+        help = "The directory the emitted ASTs are written to. Defaults to `outputs` in the project root directory."
+    )]
+    output_dir: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    options: BuildOptions,
+}
+
+impl Command for Build {
+    type Input = PathBuf;
+    type Output = ();
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        match &self.program_path {
+            Some(program_path) => Ok(program_path.clone()),
+            None => Ok(context.open_package()?.entry_path()),
+        }
+    }
+
+    fn apply(self, context: Context, program_path: Self::Input) -> Result<Self::Output> {
+        // Initialize error handler
+        let handler = Handler::default();
+
+        // Compile the program.
+        let options = OutputOptions {
+            initial_ast: self.emit.iter().any(|stage| stage == "ast"),
+            normalized_ast: self.emit.iter().any(|stage| stage == "normalized"),
+            optimized_ast: self.emit.iter().any(|stage| stage == "optimized"),
+            ast_format: self.emit_format,
+        };
+        let output_dir = match self.output_dir {
+            Some(output_dir) => output_dir,
+            None => context.dir()?.join("outputs"),
+        };
+        let mut compiler = Compiler::new(&handler, &program_path)
+            .with_compiler_options(self.options.into())
+            .with_output_options(output_dir, options);
+        // Imports are resolved against the dependencies of the project, if there is one.
+        if let Ok(package) = context.open_package() {
+            compiler = compiler.with_import_resolver(move |importer, name| package.resolve_import(importer, name));
+        }
+        compiler.compile()?;
+
+        tracing::info!(
+            "Compiled `{}` into {} constraints.",
+            program_path.display(),
+            compiler.program().constraints.len()
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conlang_ast::Program;
+    use conlang_package::{Package, Registry};
+    use conlang_span::session_globals::create_session_if_not_set_then;
+    use std::{fs, path::Path};
+    use test_dir::{DirBuilder, FileType, TestDir};
+
+    /// Builds the program at `program_path` with the command line `args`, in the directory of the program.
+    fn build(program_path: &Path, args: &[&str]) -> Result<()> {
+        let path = program_path.to_string_lossy();
+        let build = Build::try_parse_from(["build", &path].iter().chain(args)).unwrap();
+        let context = Context::new(program_path.parent().map(Path::to_path_buf), None);
+        create_session_if_not_set_then(|_| build.apply(context, program_path.to_path_buf()))
+    }
+
+    #[test]
+    fn test_emit() {
+        let dir = TestDir::temp().create("main.conlang", FileType::EmptyFile);
+        fs::write(dir.path("main.conlang"), "(x + x) * (y) = (z);\n(y) * (2 * x) = (z);\n").unwrap();
+        let output_dir = dir.path("emitted").to_string_lossy().to_string();
+
+        // The ASTs are written to the `outputs` directory of the project root directory by default.
+        build(&dir.path("main.conlang"), &["--emit", "ast,normalized,optimized"]).unwrap();
+        create_session_if_not_set_then(|_| {
+            let load = |stage: &str| Program::from_json_file(dir.path(&format!("outputs/main.{stage}.json"))).unwrap();
+            let (ast, normalized, optimized) = (load("ast"), load("normalized"), load("optimized"));
+            assert_eq!(ast.to_string(), "(x + x) * (y) = (z);\n(y) * (2 * x) = (z);\n");
+            assert_eq!(normalized.to_string(), "(2 * x) * (y) = (z);\n(y) * (2 * x) = (z);\n");
+            assert_eq!(optimized.to_string(), "(2 * x) * (y) = (z);\n");
+            // Spans are kept, so that the stages can be compared with the source.
+            assert_eq!(ast.constraints[1].span, normalized.constraints[1].span);
+        });
+
+        // The pretty-printed program can be parsed again.
+        build(
+            &dir.path("main.conlang"),
+            &["--emit", "optimized", "--emit-format", "conlang", "--output-dir", &output_dir],
+        )
+        .unwrap();
+        build(&dir.path("emitted/main.optimized.conlang"), &[]).unwrap();
+
+        // A stage whose pass does not run cannot be emitted.
+        let err = build(&dir.path("main.conlang"), &["--emit", "normalized", "--skip-pass", "normalize"]).unwrap_err();
+        assert_eq!(err.message(), "Cannot emit the normalized AST, since the `normalize` pass does not run.");
+    }
+
+    #[test]
+    fn test_nested_imports() {
+        let dir = TestDir::temp();
+        let registry = Registry::new(dir.path("registry"));
+
+        // `a` imports `b`, which imports `c`, each through its own manifest.
+        let mut dependencies = Vec::new();
+        for (name, program) in [("c", "(x) * (y) = (z);\n"), ("b", "import c as c (x = u);\n(u) * (1) = (v);\n")] {
+            let mut package = Package::initialize(name, &dir.path(name)).unwrap();
+            package.manifest.dependencies.extend(dependencies.drain(..));
+            package.manifest.write_to_dir(&package.root).unwrap();
+            fs::write(package.entry_path(), program).unwrap();
+            registry.publish(&package).unwrap();
+            dependencies.push((name.to_string(), "0.1.0".to_string()));
+        }
+        let mut package = Package::initialize("a", &dir.path("a")).unwrap();
+        package.manifest.dependencies.extend(dependencies);
+        package.manifest.write_to_dir(&package.root).unwrap();
+        package.install_dependencies(&registry).unwrap();
+        fs::write(package.entry_path(), "import b as b (u = x);\n").unwrap();
+
+        let build = Build::try_parse_from(["build"]).unwrap();
+        let context = Context::new(Some(package.root.clone()), None);
+        create_session_if_not_set_then(|_| build.apply(context, package.entry_path())).unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    commands::{BuildOptions, Command},
+    context::Context,
+};
+
+use conlang_compiler::Compiler;
+use conlang_errors::{emitter::Handler, CliError, ConlangError, Result};
+use conlang_package::Package;
+
+use clap::StructOpt;
+use std::path::PathBuf;
+use walkdir::WalkDir;
+
+/// Check programs for errors without running them
+#[derive(StructOpt, Debug)]
+pub struct Check {
+    #[structopt(
+        name = "PATHS",
+        help = "The `.conlang` files or directories to check. Defaults to the entry file of the project."
+    )]
+    paths: Vec<PathBuf>,
+
+    #[structopt(flatten)]
+    options: BuildOptions,
+}
+
+impl Command for Check {
+    type Input = Vec<PathBuf>;
+    type Output = ();
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        if self.paths.is_empty() {
+            return Ok(vec![context.open_package()?.entry_path()]);
+        }
+
+        // Directories are searched recursively for programs.
+        let mut files = Vec::new();
+        for path in self.paths.iter() {
+            if !path.is_dir() {
+                files.push(path.clone());
+                continue;
+            }
+            for entry in WalkDir::new(path).sort_by_file_name() {
+                let entry = entry.map_err(CliError::cli_io_error)?;
+                if entry.file_type().is_file() && entry.path().extension().is_some_and(|ext| ext == "conlang") {
+                    files.push(entry.into_path());
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    fn apply(self, context: Context, files: Self::Input) -> Result<Self::Output> {
+        let (mut errors, mut warnings) = (0, 0);
+        // Imports are resolved against the dependencies of the project, if there is one.
+        let package = context.open_package().ok();
+
+        // Compile each program, reporting its errors and moving on to the next.
+        for file in files.iter() {
+            // Each program gets its own handler, since the compiler stops at the first error a handler has seen.
+            let handler = Handler::default();
+            self.check_file(&handler, file, package.as_ref());
+            errors += handler.err_count();
+            warnings += handler.warning_count();
+        }
+
+        tracing::info!("Checked {} file(s) with {errors} error(s) and {warnings} warning(s).", files.len());
+
+        match errors {
+            0 => Ok(()),
+            count => Err(CliError::check_failed(count).into()),
+        }
+    }
+}
+
+impl Check {
+    /// Compiles the program at `file`, emitting its errors and warnings to the `handler`.
+    fn check_file(&self, handler: &Handler, file: &PathBuf, package: Option<&Package>) {
+        let mut compiler = Compiler::new(handler, file).with_compiler_options(self.options.clone().into());
+        if let Some(package) = package {
+            compiler = compiler.with_import_resolver(|importer, name| package.resolve_import(importer, name));
+        }
+        match compiler.compile() {
+            Ok(()) | Err(ConlangError::LastErrorCode(_)) => {}
+            Err(err) => handler.emit_err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conlang_span::session_globals::create_session_if_not_set_then;
+    use std::fs;
+    use test_dir::{DirBuilder, FileType, TestDir};
+
+    /// Returns a directory with a program with syntax errors, one with a duplicate constraint, and one with an import.
+    fn programs() -> TestDir {
+        let dir = TestDir::temp()
+            .create("syntax.conlang", FileType::EmptyFile)
+            .create("duplicate.conlang", FileType::EmptyFile)
+            .create("import.conlang", FileType::EmptyFile);
+        fs::write(dir.path("syntax.conlang"), "(x) * = (z);\n(x) * (y) = (z);\n(x y) * (y) = (z);\n").unwrap();
+        fs::write(dir.path("duplicate.conlang"), "(x) * (y) = (z);\n(y) * (x) = (z);\n").unwrap();
+        fs::write(dir.path("import.conlang"), "import square as s;\n(x) * (y) = (z);\n").unwrap();
+        dir
+    }
+
+    /// Returns the error and warning messages of checking the program at `path`.
+    fn diagnostics(path: &PathBuf) -> (Vec<String>, Vec<String>) {
+        let check = Check::try_parse_from(["check", &path.to_string_lossy()]).unwrap();
+        create_session_if_not_set_then(|_| {
+            let (handler, buf) = Handler::new_with_buf();
+            check.check_file(&handler, path, None);
+            let errors = buf.extract_errs().into_inner().iter().map(|err| err.message()).collect();
+            let warnings =
+                buf.extract_warnings().into_inner().iter().map(|warning| warning.message().to_string()).collect();
+            (errors, warnings)
+        })
+    }
+
+    #[test]
+    fn test_check_file() {
+        let dir = programs();
+
+        let (errors, warnings) = diagnostics(&dir.path("syntax.conlang"));
+        assert_eq!(errors, ["expected ( -- found '='"]);
+        assert!(warnings.is_empty());
+
+        let (errors, warnings) = diagnostics(&dir.path("duplicate.conlang"));
+        assert!(errors.is_empty());
+        assert_eq!(warnings, ["This constraint is equivalent to an earlier one, and is removed."]);
+
+        // Imports cannot be resolved outside of a project.
+        let (errors, _) = diagnostics(&dir.path("import.conlang"));
+        assert_eq!(errors, ["Cannot resolve the import of `square`."]);
+
+        let (errors, _) = diagnostics(&dir.path("missing.conlang"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Cannot read from the provided file path"));
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = programs();
+        let context = Context::new(Some(dir.root().to_path_buf()), None);
+
+        // The programs in a directory are all checked, and their errors are counted.
+        let check = Check::try_parse_from(["check", &dir.root().to_string_lossy()]).unwrap();
+        let files = check.prelude(context.clone()).unwrap();
+        assert_eq!(files.len(), 3);
+        let err = create_session_if_not_set_then(|_| check.apply(context.clone(), files)).unwrap_err();
+        assert_eq!(err.message(), "Checking failed with 2 error(s).");
+
+        let check = Check::try_parse_from(["check", &dir.path("duplicate.conlang").to_string_lossy()]).unwrap();
+        let files = check.prelude(context.clone()).unwrap();
+        assert!(create_session_if_not_set_then(|_| check.apply(context, files)).is_ok());
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    commands::{BuildOptions, Command},
+    context::Context,
+};
+
+use conlang_compiler::{AstFormat, Compiler};
+use conlang_errors::{emitter::Handler, CliError, Result};
+
+use clap::StructOpt;
+use std::{fs, path::PathBuf};
+
+/// Export the compiled circuit of a program
+#[derive(StructOpt, Debug)]
+pub struct Export {
+    #[structopt(name = "PATH", help = "The path to the `.conlang` file. Defaults to the entry file of the project.")]
+    program_path: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        default_value = "json",
+        possible_values = &["json", "conlang"],
+        help = "The format of the circuit: its serialized AST, which `run` can load, or a conlang program."
+    )]
+    format: AstFormat,
+
+    #[structopt(
+        short,
+        long,
+        help = "The file the circuit is written to. Defaults to `outputs/<name>.<format>` in the project root \
+                directory, where `<name>` is the name of the program file."
+    )]
+    output: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    options: BuildOptions,
+}
+
+impl Command for Export {
+    type Input = PathBuf;
+    type Output = ();
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        match &self.program_path {
+            Some(program_path) => Ok(program_path.clone()),
+            None => Ok(context.open_package()?.entry_path()),
+        }
+    }
+
+    fn apply(self, context: Context, program_path: Self::Input) -> Result<Self::Output> {
+        // Initialize error handler
+        let handler = Handler::default();
+
+        // Compile the program.
+        let mut compiler = Compiler::new(&handler, &program_path).with_compiler_options(self.options.into());
+        // Imports are resolved against the dependencies of the project, if there is one.
+        if let Ok(package) = context.open_package() {
+            compiler = compiler.with_import_resolver(move |importer, name| package.resolve_import(importer, name));
+        }
+        compiler.compile()?;
+
+        // A conlang program cannot express the eliminated variables, which evaluation needs to check the inputs.
+        let eliminated = &compiler.program().eliminated;
+        if self.format == AstFormat::Conlang && !eliminated.is_empty() {
+            return Err(CliError::cannot_export_eliminated_variables(eliminated.len()).into());
+        }
+
+        // Write the compiled program.
+        let output = match self.output {
+            Some(output) => output,
+            None => {
+                let name = program_path.file_stem().unwrap_or_default().to_string_lossy();
+                context.dir()?.join("outputs").join(format!("{name}.{}", self.format.extension()))
+            }
+        };
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent).map_err(CliError::cli_io_error)?;
+        }
+        let contents = match self.format {
+            AstFormat::Json => compiler.program().to_json_string()?,
+            AstFormat::Conlang => compiler.program().to_string(),
+        };
+        fs::write(&output, contents).map_err(CliError::cli_io_error)?;
+
+        tracing::info!("Exported {} constraints to `{}`.", compiler.program().constraints.len(), output.display());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conlang_ast::Program;
+    use conlang_package::Package;
+    use conlang_span::session_globals::create_session_if_not_set_then;
+    use test_dir::{DirBuilder, TestDir};
+
+    /// Exports the program of the project at `root` with the command line `args`.
+    fn export(root: PathBuf, args: &[&str]) -> Result<()> {
+        let export = Export::try_parse_from(["export"].iter().chain(args)).unwrap();
+        let context = Context::new(Some(root), None);
+        let program_path = export.prelude(context.clone())?;
+        create_session_if_not_set_then(|_| export.apply(context, program_path))
+    }
+
+    #[test]
+    fn test_export() {
+        let dir = TestDir::temp();
+        let root = dir.path("project");
+        Package::initialize("project", &root).unwrap();
+
+        // The circuit is written to the `outputs` directory of the project root directory by default.
+        export(root.clone(), &[]).unwrap();
+        create_session_if_not_set_then(|_| {
+            let program = Program::from_json_file(root.join("outputs/main.json")).unwrap();
+            assert_eq!(program.to_string(), "(x) * (y) = (z);\n");
+        });
+
+        // A conlang program is written to the given file.
+        let output = dir.path("circuit.conlang");
+        export(root.clone(), &["--format", "conlang", "-o", &output.to_string_lossy()]).unwrap();
+        assert_eq!(fs::read_to_string(&output).unwrap(), "(x) * (y) = (z);\n");
+
+        // A program with eliminated variables is only exported as JSON.
+        fs::write(root.join("src/main.conlang"), "(x) * (1) = (y);\n(y) * (y) = (z);\n").unwrap();
+        let err = export(root.clone(), &["-O", "2", "--format", "conlang"]).unwrap_err();
+        assert!(err.to_string().contains("Cannot export a conlang program"), "{err}");
+        export(root, &["-O", "2"]).unwrap();
+    }
+}
@@ -0,0 +1,464 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use conlang_ast::{Assignment, Program, Variable};
+use conlang_compiler::{EvaluationOptions, Evaluator};
+use conlang_errors::{emitter::Handler, Formatted, Result};
+use conlang_parser::{format_source, is_identifier};
+use conlang_passes::{Reconstructor, Replacer};
+use conlang_span::{
+    session_globals::{create_session_then, with_session_globals},
+    source_map::{FileName, SourceFile},
+    span::BytePos,
+    Span,
+    Symbol,
+};
+
+use lsp_types::{
+    Diagnostic,
+    DiagnosticRelatedInformation,
+    DiagnosticSeverity,
+    Hover,
+    HoverContents,
+    Location,
+    MarkupContent,
+    MarkupKind,
+    NumberOrString,
+    Position,
+    Range,
+    TextEdit,
+    Url,
+    WorkspaceEdit,
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+/// The file extension of conlang programs.
+const PROGRAM_EXTENSION: &str = "conlang";
+/// The file extension of input files.
+const INPUT_EXTENSION: &str = "in";
+
+/// The documents opened in the editor.
+/// A program is analyzed together with its input, which is the `.in` file next to it.
+#[derive(Default)]
+pub struct Workspace {
+    /// The contents of the open documents, which take precedence over the files on disk.
+    documents: HashMap<PathBuf, String>,
+}
+
+impl Workspace {
+    /// Sets the contents of the document at `uri`.
+    pub fn open(&mut self, uri: &Url, text: String) {
+        if let Ok(path) = uri.to_file_path() {
+            self.documents.insert(path, text);
+        }
+    }
+
+    /// Forgets the contents of the document at `uri`.
+    pub fn close(&mut self, uri: &Url) {
+        if let Ok(path) = uri.to_file_path() {
+            self.documents.remove(&path);
+        }
+    }
+
+    /// Returns the URIs of the open documents whose diagnostics depend on the document at `uri`.
+    /// These are the document itself, if it is open, and the open documents that it is the program or input of.
+    pub fn dependents(&self, uri: &Url) -> Vec<Url> {
+        let Ok(path) = uri.to_file_path() else {
+            return Vec::new();
+        };
+        self.documents
+            .keys()
+            .filter(|document| {
+                *document == &path
+                    || self.files(document).is_some_and(|(program, input)| {
+                        program.as_ref() == Some(&path) || input.as_ref() == Some(&path)
+                    })
+            })
+            .filter_map(|document| Url::from_file_path(document).ok())
+            .collect()
+    }
+
+    /// Returns the errors and warnings of the document at `uri`.
+    pub fn diagnostics(&self, uri: &Url) -> Vec<Diagnostic> {
+        self.analyze(uri, |analysis, file| analysis.diagnostics(file)).unwrap_or_default()
+    }
+
+    /// Returns the value of the variable at `position` in the program at `uri`.
+    pub fn hover(&self, uri: &Url, position: Position) -> Option<Hover> {
+        self.analyze(uri, |analysis, file| {
+            let pos = file.utf16_line_col_to_byte_pos(position.line as usize, position.character as usize)?;
+            let program = analysis.program.as_ref().filter(|program| Rc::ptr_eq(&program.file, &file))?;
+            let variable = program.ast.as_ref()?.variables().find(|variable| contains(variable.span, pos))?;
+
+            let value = match analysis.input.as_ref().and_then(|input| input.ast.as_ref()) {
+                Some(assignment) => match assignment.map.get(&variable.name) {
+                    Some(constant) => format!("`{variable}` = `{}`", constant.value),
+                    None => format!("`{variable}` is not assigned"),
+                },
+                None => format!("`{variable}` has no input file"),
+            };
+
+            Some(Hover {
+                contents: HoverContents::Markup(MarkupContent { kind: MarkupKind::Markdown, value }),
+                range: Some(range(&file, variable.span)),
+            })
+        })?
+    }
+
+    /// Returns the locations that the variable at `position` in the document at `uri` refers to.
+    /// A variable in a program refers to its assignment, and an assigned variable refers to its uses.
+    pub fn definition(&self, uri: &Url, position: Position) -> Option<Vec<Location>> {
+        self.analyze(uri, |analysis, file| {
+            let pos = file.utf16_line_col_to_byte_pos(position.line as usize, position.character as usize)?;
+            let (name, _) = analysis.variable_at(&file, pos)?;
+            let locations: Vec<_> = analysis
+                .occurrences(name)
+                .into_iter()
+                .filter(|occurrence| !file.contains(*occurrence))
+                .filter_map(|occurrence| analysis.location(occurrence))
+                .collect();
+            Some(locations)
+        })?
+    }
+
+    /// Renames the variable at `position` in the document at `uri` to `new_name`,
+    /// in both the program and its input.
+    pub fn rename(
+        &self,
+        uri: &Url,
+        position: Position,
+        new_name: &str,
+    ) -> Option<std::result::Result<WorkspaceEdit, String>> {
+        self.analyze(uri, |analysis, file| {
+            let pos = file.utf16_line_col_to_byte_pos(position.line as usize, position.character as usize)?;
+            let (name, _) = analysis.variable_at(&file, pos)?;
+
+            if !is_identifier(new_name) {
+                return Some(Err(format!("`{new_name}` is not a valid variable name")));
+            }
+            if name.as_str() != new_name && !analysis.occurrences(Symbol::intern(new_name)).is_empty() {
+                return Some(Err(format!("A variable named `{new_name}` already exists")));
+            }
+
+            let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+            for location in
+                analysis.rename(name, Symbol::intern(new_name)).into_iter().filter_map(|span| analysis.location(span))
+            {
+                changes.entry(location.uri).or_default().push(TextEdit::new(location.range, new_name.to_string()));
+            }
+            Some(Ok(WorkspaceEdit::new(changes)))
+        })?
+    }
+
+    /// Returns the edit that formats the program at `uri`, or `None` if it does not parse.
+    pub fn format(&self, uri: &Url) -> Option<Vec<TextEdit>> {
+        let path = uri.to_file_path().ok()?;
+        if path.extension()? != PROGRAM_EXTENSION {
+            return None;
+        }
+        let text = self.text(&path)?;
+
+        create_session_then(|_| {
+            let formatted = format_source(&text).ok()?;
+            let file = with_session_globals(|s| s.source_map.new_source(&text, FileName::Real(path)));
+            Some(vec![TextEdit::new(range(&file, Span::new(file.start_pos, file.end_pos)), formatted)])
+        })
+    }
+
+    /// Returns the contents of the document at `path`, or of the file on disk if it is not open.
+    fn text(&self, path: &Path) -> Option<String> {
+        match self.documents.get(path) {
+            Some(text) => Some(text.clone()),
+            None => fs::read_to_string(path).ok(),
+        }
+    }
+
+    /// Returns the file with the `extension` that belongs to the file at `path`.
+    /// This is the file with the same name, or else the only file with the `extension` in the directory.
+    fn sibling(&self, path: &Path, extension: &str) -> Option<PathBuf> {
+        let candidate = path.with_extension(extension);
+        if self.documents.contains_key(&candidate) || candidate.is_file() {
+            return Some(candidate);
+        }
+
+        let mut siblings = fs::read_dir(path.parent()?)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|sibling| sibling.is_file() && sibling.extension().is_some_and(|ext| ext == extension));
+        match (siblings.next(), siblings.next()) {
+            (Some(sibling), None) => Some(sibling),
+            _ => None,
+        }
+    }
+
+    /// Analyzes the document at `uri` together with its program or input in a new session,
+    /// and then runs `f` with the analysis and the source file of the document.
+    fn analyze<R>(&self, uri: &Url, f: impl FnOnce(&Analysis, Rc<SourceFile>) -> R) -> Option<R> {
+        let path = uri.to_file_path().ok()?;
+        let is_program = path.extension()? == PROGRAM_EXTENSION;
+        let (program_path, input_path) = self.files(&path)?;
+
+        create_session_then(|_| {
+            let (handler, buf) = Handler::new_with_buf();
+            let program = program_path.and_then(|path| self.parse(&handler, path, conlang_parser::parse_program));
+            let input = input_path.and_then(|path| self.parse(&handler, path, conlang_parser::parse_input));
+
+            // Check the input against the program, if both are well-formed.
+            if let (Some(Parsed { ast: Some(program), .. }), Some(Parsed { ast: Some(assignment), .. })) =
+                (&program, &input)
+            {
+                if !handler.had_errors() {
+                    Evaluator::check_unused_inputs(&handler, &program.variable_names(), assignment);
+                    if let Err(err) =
+                        Evaluator::check_assignment(&handler, program, assignment, &EvaluationOptions::default())
+                    {
+                        handler.emit_err(err);
+                    }
+                }
+            }
+
+            let mut diagnostics = Vec::new();
+            for err in buf.extract_errs().into_inner() {
+                let code = err.formatted().map(Formatted::error_code).unwrap_or_else(|| err.error_code());
+                diagnostics.push((err.formatted().cloned(), code, err.message(), DiagnosticSeverity::ERROR));
+            }
+            for warning in buf.extract_warnings().into_inner() {
+                let code = warning.warning_code();
+                diagnostics.push((warning.formatted().cloned(), code, warning.message(), DiagnosticSeverity::WARNING));
+            }
+
+            let file = match is_program {
+                true => program.as_ref()?.file.clone(),
+                false => input.as_ref()?.file.clone(),
+            };
+            Some(f(&Analysis { program, input, diagnostics }, file))
+        })
+    }
+
+    /// Returns the paths of the program and the input that the document at `path` is analyzed with,
+    /// one of which is the document itself, or `None` if it is neither a program nor an input.
+    fn files(&self, path: &Path) -> Option<(Option<PathBuf>, Option<PathBuf>)> {
+        match path.extension()?.to_str()? {
+            PROGRAM_EXTENSION => Some((Some(path.to_path_buf()), self.sibling(path, INPUT_EXTENSION))),
+            INPUT_EXTENSION => Some((self.sibling(path, PROGRAM_EXTENSION), Some(path.to_path_buf()))),
+            _ => None,
+        }
+    }
+
+    /// Registers the file at `path` in the source map and parses it, emitting errors through the `handler`.
+    fn parse<T>(
+        &self,
+        handler: &Handler,
+        path: PathBuf,
+        parse: impl FnOnce(&Handler, &str, BytePos) -> Result<T>,
+    ) -> Option<Parsed<T>> {
+        let text = self.text(&path)?;
+        let file = with_session_globals(|s| s.source_map.new_source(&text, FileName::Real(path)));
+        let ast = match parse(handler, &file.src, file.start_pos) {
+            Ok(ast) => Some(ast),
+            Err(err) => {
+                handler.emit_err(err);
+                None
+            }
+        };
+        Some(Parsed { file, ast })
+    }
+}
+
+/// A file registered in the source map of the current session, and its AST if it parsed.
+struct Parsed<T> {
+    file: Rc<SourceFile>,
+    ast: Option<T>,
+}
+
+/// A message emitted while analyzing a program and its input.
+/// Holds the message location, code, text, and severity.
+type Message = (Option<Formatted>, String, String, DiagnosticSeverity);
+
+/// The result of analyzing a program and its input.
+struct Analysis {
+    program: Option<Parsed<Program>>,
+    input: Option<Parsed<Assignment>>,
+    diagnostics: Vec<Message>,
+}
+
+impl Analysis {
+    /// Returns the diagnostics that point into `file`.
+    /// Messages that do not point at a span are reported at the start of the file.
+    fn diagnostics(&self, file: Rc<SourceFile>) -> Vec<Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|(formatted, ..)| formatted.as_ref().is_none_or(|formatted| file.contains(formatted.span)))
+            .map(|(formatted, code, message, severity)| {
+                let related_information = formatted.as_ref().map(|formatted| {
+                    formatted
+                        .labels
+                        .iter()
+                        .filter_map(|label| {
+                            let location = self.location(label.span)?;
+                            Some(DiagnosticRelatedInformation { location, message: label.message.clone() })
+                        })
+                        .collect()
+                });
+                let range = formatted.as_ref().map(|formatted| range(&file, formatted.span)).unwrap_or_default();
+                Diagnostic {
+                    range,
+                    severity: Some(*severity),
+                    code: Some(NumberOrString::String(code.clone())),
+                    source: Some("conlang".to_string()),
+                    message: message.clone(),
+                    related_information,
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the name and span of the variable at `pos` in `file`, if there is one.
+    fn variable_at(&self, file: &Rc<SourceFile>, pos: BytePos) -> Option<(Symbol, Span)> {
+        if let Some(Parsed { file: program_file, ast: Some(program) }) = &self.program {
+            if Rc::ptr_eq(program_file, file) {
+                let variable = program.variables().find(|variable| contains(variable.span, pos))?;
+                return Some((variable.name, variable.span));
+            }
+        }
+        if let Some(Parsed { file: input_file, ast: Some(assignment) }) = &self.input {
+            if Rc::ptr_eq(input_file, file) {
+                let (name, span) = assignment.spans.iter().find(|(_, span)| contains(**span, pos))?;
+                return Some((*name, *span));
+            }
+        }
+        None
+    }
+
+    /// Returns the spans of the variable `name` in the program and its input.
+    fn occurrences(&self, name: Symbol) -> Vec<Span> {
+        let mut spans = Vec::new();
+        if let Some(Parsed { ast: Some(program), .. }) = &self.program {
+            spans.extend(program.variables().filter(|variable| variable.name == name).map(|variable| variable.span));
+        }
+        if let Some(Parsed { ast: Some(assignment), .. }) = &self.input {
+            spans.extend(assignment.spans.get(&name));
+        }
+        spans
+    }
+
+    /// Returns the spans of the variable `name` that are renamed to `new_name` in the program and its input.
+    /// The program is renamed by a [`Replacer`], which records the spans of the variables it replaces.
+    fn rename(&self, name: Symbol, new_name: Symbol) -> Vec<Span> {
+        let spans = RefCell::new(Vec::new());
+        if let Some(Parsed { ast: Some(program), .. }) = &self.program {
+            let mut replacer = Replacer::new(|variable: &Variable| match variable.name == name {
+                true => {
+                    spans.borrow_mut().push(variable.span);
+                    Variable { name: new_name, ..variable.clone() }
+                }
+                false => variable.clone(),
+            });
+            replacer.reconstruct(program.clone());
+        }
+
+        let mut spans = spans.into_inner();
+        if let Some(Parsed { ast: Some(assignment), .. }) = &self.input {
+            spans.extend(assignment.spans.get(&name));
+        }
+        spans
+    }
+
+    /// Returns the location of `span` in the program or its input.
+    fn location(&self, span: Span) -> Option<Location> {
+        let file = [self.program.as_ref().map(|parsed| &parsed.file), self.input.as_ref().map(|parsed| &parsed.file)]
+            .into_iter()
+            .flatten()
+            .find(|file| file.contains(span))?;
+        let FileName::Real(path) = &file.name else {
+            return None;
+        };
+        Some(Location::new(Url::from_file_path(path).ok()?, range(file, span)))
+    }
+}
+
+/// Returns whether `pos` lies within `span`, including its end.
+fn contains(span: Span, pos: BytePos) -> bool {
+    span.lo <= pos && pos <= span.hi
+}
+
+/// Returns the range of `span` in `file`.
+fn range(file: &SourceFile, span: Span) -> Range {
+    let position = |pos| {
+        let (line, col) = file.utf16_line_col(pos);
+        Position::new(line as u32, col as u32)
+    };
+    Range::new(position(span.lo), position(span.hi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, FileType, TestDir};
+
+    /// Returns the URI of the file `name` in the `dir`.
+    fn uri(dir: &TestDir, name: &str) -> Url {
+        Url::from_file_path(dir.path(name)).unwrap()
+    }
+
+    #[test]
+    fn test_dependents() {
+        let dir = ["a.conlang", "a.in", "b.conlang", "b.in"]
+            .iter()
+            .fold(TestDir::temp(), |dir, name| dir.create(name, FileType::EmptyFile));
+        let mut workspace = Workspace::default();
+        for name in ["a.conlang", "a.in", "b.conlang"] {
+            workspace.open(&uri(&dir, name), String::new());
+        }
+
+        // A program and its input depend on each other, but not on other documents.
+        let mut dependents = workspace.dependents(&uri(&dir, "a.conlang"));
+        dependents.sort();
+        assert_eq!(dependents, [uri(&dir, "a.conlang"), uri(&dir, "a.in")]);
+        assert_eq!(workspace.dependents(&uri(&dir, "b.in")), [uri(&dir, "b.conlang")]);
+
+        // A closed document is no longer a dependent.
+        workspace.close(&uri(&dir, "a.in"));
+        assert_eq!(workspace.dependents(&uri(&dir, "a.in")), [uri(&dir, "a.conlang")]);
+    }
+
+    #[test]
+    fn test_rename_composed_variable() {
+        let dir = TestDir::temp().create("main.conlang", FileType::EmptyFile).create("main.in", FileType::EmptyFile);
+        let mut workspace = Workspace::default();
+        workspace.open(&uri(&dir, "main.conlang"), "(x) * (sq.output) = (y);\n".to_string());
+        workspace.open(&uri(&dir, "main.in"), "x = 2;\nsq.output = 4;\ny = 8;\n".to_string());
+
+        // Variables of imported programs have dotted names, which they can also be renamed to.
+        let edit = workspace.rename(&uri(&dir, "main.conlang"), Position::new(0, 9), "sq.result").unwrap().unwrap();
+        let changes = edit.changes.unwrap();
+        assert_eq!(changes[&uri(&dir, "main.conlang")], [TextEdit::new(
+            Range::new(Position::new(0, 7), Position::new(0, 16)),
+            "sq.result".to_string()
+        )]);
+        assert_eq!(changes[&uri(&dir, "main.in")].len(), 1);
+
+        let error = workspace.rename(&uri(&dir, "main.conlang"), Position::new(0, 1), "x.").unwrap().unwrap_err();
+        assert_eq!(error, "`x.` is not a valid variable name");
+    }
+}
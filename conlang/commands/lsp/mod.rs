@@ -0,0 +1,232 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+mod analysis;
+use analysis::Workspace;
+
+use crate::{commands::Command, context::Context};
+
+use conlang_errors::{CliError, Result};
+
+use clap::StructOpt;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument,
+        DidCloseTextDocument,
+        DidOpenTextDocument,
+        DidSaveTextDocument,
+        Notification as _,
+        PublishDiagnostics,
+    },
+    request::{Formatting, GotoDefinition, HoverRequest, Rename, Request as _},
+    DocumentFormattingParams,
+    GotoDefinitionParams,
+    GotoDefinitionResponse,
+    HoverParams,
+    HoverProviderCapability,
+    OneOf,
+    PublishDiagnosticsParams,
+    RenameParams,
+    ServerCapabilities,
+    TextDocumentSyncCapability,
+    TextDocumentSyncKind,
+    TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions,
+    Url,
+};
+use serde::de::DeserializeOwned;
+
+/// Start a language server over stdio
+#[derive(StructOpt, Debug)]
+pub struct Lsp {}
+
+impl Command for Lsp {
+    type Input = ();
+    type Output = ();
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, _: Context, _: Self::Input) -> Result<Self::Output> {
+        let (connection, io_threads) = Connection::stdio();
+
+        let capabilities = ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                ..Default::default()
+            })),
+            hover_provider: Some(HoverProviderCapability::Simple(true)),
+            definition_provider: Some(OneOf::Left(true)),
+            rename_provider: Some(OneOf::Left(true)),
+            document_formatting_provider: Some(OneOf::Left(true)),
+            ..Default::default()
+        };
+        let capabilities = serde_json::to_value(capabilities).map_err(CliError::language_server_error)?;
+        connection.initialize(capabilities).map_err(CliError::language_server_error)?;
+
+        LanguageServer { connection: &connection, workspace: Workspace::default() }.run()?;
+
+        drop(connection);
+        io_threads.join().map_err(CliError::language_server_error)?;
+        Ok(())
+    }
+}
+
+/// The state of a running language server.
+struct LanguageServer<'a> {
+    /// The connection to the editor.
+    connection: &'a Connection,
+    /// The documents opened in the editor.
+    workspace: Workspace,
+}
+
+impl LanguageServer<'_> {
+    /// Handles messages from the editor until it requests a shutdown.
+    fn run(&mut self) -> Result<()> {
+        for message in &self.connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request).map_err(CliError::language_server_error)? {
+                        break;
+                    }
+                    let response = self.handle_request(request);
+                    self.send(response.into())?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the response to a `request`.
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            HoverRequest::METHOD => params::<HoverParams>(request)
+                .map(|params| {
+                    let position = params.text_document_position_params;
+                    self.workspace.hover(&position.text_document.uri, position.position)
+                })
+                .and_then(to_value),
+            GotoDefinition::METHOD => params::<GotoDefinitionParams>(request)
+                .map(|params| {
+                    let position = params.text_document_position_params;
+                    self.workspace
+                        .definition(&position.text_document.uri, position.position)
+                        .map(GotoDefinitionResponse::Array)
+                })
+                .and_then(to_value),
+            Rename::METHOD => params::<RenameParams>(request).and_then(|params| {
+                let position = params.text_document_position;
+                match self.workspace.rename(&position.text_document.uri, position.position, &params.new_name) {
+                    Some(Err(message)) => Err((ErrorCode::RequestFailed, message)),
+                    edit => to_value(edit.and_then(|edit| edit.ok())),
+                }
+            }),
+            Formatting::METHOD => params::<DocumentFormattingParams>(request)
+                .map(|params| self.workspace.format(&params.text_document.uri))
+                .and_then(to_value),
+            method => Err((ErrorCode::MethodNotFound, format!("Unsupported request `{method}`"))),
+        };
+
+        match result {
+            Ok(value) => Response::new_ok(id, value),
+            Err((code, message)) => Response::new_err(id, code as i32, message),
+        }
+    }
+
+    /// Updates the workspace for a `notification` and republishes diagnostics.
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                if let Ok(params) = notification_params::<DidOpenTextDocument>(notification) {
+                    self.workspace.open(&params.text_document.uri, params.text_document.text);
+                    self.publish_dependent_diagnostics(&params.text_document.uri)?;
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                if let Ok(params) = notification_params::<DidChangeTextDocument>(notification) {
+                    // With full synchronization, the last change holds the entire document.
+                    if let Some(change) = params.content_changes.into_iter().last() {
+                        self.workspace.open(&params.text_document.uri, change.text);
+                        self.publish_dependent_diagnostics(&params.text_document.uri)?;
+                    }
+                }
+            }
+            DidSaveTextDocument::METHOD => {
+                if let Ok(params) = notification_params::<DidSaveTextDocument>(notification) {
+                    self.publish_dependent_diagnostics(&params.text_document.uri)?;
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                if let Ok(params) = notification_params::<DidCloseTextDocument>(notification) {
+                    self.workspace.close(&params.text_document.uri);
+                    // Clear the diagnostics of the closed document.
+                    self.publish_dependent_diagnostics(&params.text_document.uri)?;
+                    self.publish_diagnostics(params.text_document.uri, Vec::new())?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Publishes the diagnostics of the open documents that depend on the document at `uri`.
+    /// A change to a program or an input can affect the diagnostics of the other, but of no other document.
+    fn publish_dependent_diagnostics(&self, uri: &Url) -> Result<()> {
+        for uri in self.workspace.dependents(uri) {
+            let diagnostics = self.workspace.diagnostics(&uri);
+            self.publish_diagnostics(uri, diagnostics)?;
+        }
+        Ok(())
+    }
+
+    /// Publishes the `diagnostics` of the document at `uri`.
+    fn publish_diagnostics(&self, uri: Url, diagnostics: Vec<lsp_types::Diagnostic>) -> Result<()> {
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        self.send(Notification::new(PublishDiagnostics::METHOD.to_string(), params).into())
+    }
+
+    /// Sends a `message` to the editor.
+    fn send(&self, message: Message) -> Result<()> {
+        self.connection.sender.send(message).map_err(|err| CliError::language_server_error(err).into())
+    }
+}
+
+/// The error code and message of a failed request.
+type RequestError = (ErrorCode, String);
+
+/// Deserializes the parameters of a `request`.
+fn params<P: DeserializeOwned>(request: Request) -> std::result::Result<P, RequestError> {
+    serde_json::from_value(request.params).map_err(|err| (ErrorCode::InvalidParams, err.to_string()))
+}
+
+/// Deserializes the parameters of a `notification`.
+fn notification_params<N: lsp_types::notification::Notification>(
+    notification: Notification,
+) -> serde_json::Result<N::Params> {
+    serde_json::from_value(notification.params)
+}
+
+/// Serializes the result of a request.
+fn to_value<T: serde::Serialize>(result: T) -> std::result::Result<serde_json::Value, RequestError> {
+    serde_json::to_value(result).map_err(|err| (ErrorCode::InternalError, err.to_string()))
+}
@@ -14,11 +14,44 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod add;
+pub use add::Add;
+
+pub mod build;
+pub use build::Build;
+
+pub mod check;
+pub use check::Check;
+
+pub mod export;
+pub use export::Export;
+
+pub mod lsp;
+pub use lsp::Lsp;
+
+pub mod new;
+pub use new::New;
+
+pub mod publish;
+pub use publish::Publish;
+
+pub mod repl;
+pub use repl::Repl;
+
 pub mod run;
 pub use run::Run;
 
+pub mod stats;
+pub use stats::Stats;
+
+use crate::context::Context;
+
+use conlang_compiler::CompilerOptions;
 use conlang_errors::Result;
 
+use clap::StructOpt;
+use std::time::Instant;
+
 /// Base trait for the Conlang CLI, see methods and their documentation for details.
 pub trait Command {
     /// If the current command requires running another command beforehand
@@ -32,33 +65,81 @@ pub trait Command {
     type Output;
 
     /// Runs the prelude and returns the Input of the current command.
-    fn prelude(&self) -> Result<Self::Input>
+    fn prelude(&self, context: Context) -> Result<Self::Input>
     where
         Self: std::marker::Sized;
 
     /// Runs the main operation of this command. This function is run within
     /// context of 'execute' function, which sets logging and timers.
-    fn apply(self, input: Self::Input) -> Result<Self::Output>
+    fn apply(self, context: Context, input: Self::Input) -> Result<Self::Output>
     where
         Self: std::marker::Sized;
 
     /// A wrapper around the `apply` method.
     /// This function sets up tracing, timing, and the context.
-    fn execute(self) -> Result<Self::Output>
+    fn execute(self, context: Context) -> Result<Self::Output>
     where
         Self: std::marker::Sized,
     {
-        let input = self.prelude()?;
-        self.apply(input)
+        let start = Instant::now();
+
+        let input = self.prelude(context.clone())?;
+        let output = self.apply(context, input);
+
+        tracing::debug!("Finished in {:.2?}", start.elapsed());
+        output
     }
 
     /// Executes command but empty the result. Comes in handy where there's a
     /// need to make match arms compatible while keeping implementation-specific
     /// output possible. Errors however are all of the type Error
-    fn try_execute(self) -> Result<()>
+    fn try_execute(self, context: Context) -> Result<()>
     where
         Self: std::marker::Sized,
     {
-        self.execute().map(|_| Ok(()))?
+        self.execute(context).map(|_| Ok(()))?
+    }
+}
+
+/// Compiler options shared by the commands that compile a program.
+#[derive(StructOpt, Clone, Debug)]
+pub struct BuildOptions {
+    #[structopt(
+        short = 'O',
+        default_value = "1",
+        help = "The optimization level: `0` disables optimizations, `1` folds constants and removes duplicate \
+                constraints, and `2` also eliminates \
+                variables using linear constraints."
+    )]
+    pub optimization_level: u8,
+
+    #[structopt(
+        long = "public",
+        use_value_delimiter = true,
+        help = "Variables that optimizations must keep in the program, e.g. `--public x,y`."
+    )]
+    pub public_variables: Vec<String>,
+
+    #[structopt(
+        long,
+        use_value_delimiter = true,
+        help = "The passes to run instead of those of the optimization level, e.g. `--passes=normalize,fold,dedup`. \
+                Passes run after the passes they depend on."
+    )]
+    pub passes: Option<Vec<String>>,
+
+    #[structopt(long = "skip-pass", use_value_delimiter = true, help = "Passes not to run, e.g. `--skip-pass=dedup`.")]
+    pub skipped_passes: Vec<String>,
+}
+
+impl From<BuildOptions> for CompilerOptions {
+    fn from(options: BuildOptions) -> Self {
+        Self {
+            optimization_level: options.optimization_level,
+            public_variables: options.public_variables,
+            // `--passes=` runs no passes, rather than a pass with an empty name.
+            passes: options.passes.map(|passes| passes.into_iter().filter(|name| !name.is_empty()).collect()),
+            skipped_passes: options.skipped_passes,
+        }
     }
 }
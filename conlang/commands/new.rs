@@ -0,0 +1,77 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+
+use conlang_errors::Result;
+use conlang_package::Package;
+
+use clap::StructOpt;
+
+/// Create a new project
+#[derive(StructOpt, Debug)]
+pub struct New {
+    #[structopt(name = "NAME", help = "The name of the project, which is also the name of its directory.")]
+    name: String,
+}
+
+impl Command for New {
+    type Input = ();
+    type Output = ();
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let path = context.dir()?.join(&self.name);
+        Package::initialize(&self.name, &path)?;
+
+        tracing::info!("Created project `{}` in `{}`.", self.name, path.display());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conlang_package::Manifest;
+    use test_dir::{DirBuilder, TestDir};
+
+    /// Creates the project called `name` in the directory at `dir`.
+    fn new(dir: &TestDir, name: &str) -> Result<()> {
+        let new = New::try_parse_from(["new", name]).unwrap();
+        new.apply(Context::new(Some(dir.root().to_path_buf()), None), ())
+    }
+
+    #[test]
+    fn test_new() {
+        let dir = TestDir::temp();
+        new(&dir, "project").unwrap();
+
+        let package = Package::open(&dir.path("project")).unwrap();
+        assert_eq!(package.manifest, Manifest::new("project"));
+        assert!(package.entry_path().is_file());
+        assert!(package.input_paths().iter().all(|path| path.is_file()));
+
+        // An existing project is not overwritten, and a name must be valid.
+        let err = new(&dir, "project").unwrap_err();
+        assert!(err.message().contains("already exists"), "{}", err.message());
+        assert!(new(&dir, "1project").is_err());
+        assert!(!dir.path("1project").exists());
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+
+use conlang_errors::Result;
+use conlang_package::Package;
+
+use clap::StructOpt;
+
+/// Publish the project to the package registry
+#[derive(StructOpt, Debug)]
+pub struct Publish {}
+
+impl Command for Publish {
+    type Input = Package;
+    type Output = ();
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        context.open_package()
+    }
+
+    fn apply(self, context: Context, package: Self::Input) -> Result<Self::Output> {
+        let path = context.registry()?.publish(&package)?;
+
+        let metadata = &package.manifest.package;
+        tracing::info!("Published `{}` v{} to `{}`.", metadata.name, metadata.version, path.display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+
+use conlang_ast::{Assignment, Program};
+use conlang_compiler::{EvaluationOptions, Evaluator, Values};
+use conlang_errors::{emitter::Handler, CliError, Result};
+use conlang_span::{session_globals::with_session_globals, source_map::FileName, span::BytePos};
+
+use clap::StructOpt;
+use std::io::{self, BufRead, Write};
+
+/// The help message printed by the `:help` command.
+const HELP: &str = "\
+Enter a constraint `(A) * (B) = (C);` to add it to the program,
+or an assignment `x = 5;` to set the value of a variable.
+
+Commands:
+  :eval <linear combination>  Evaluates a linear combination, e.g. `:eval 2 * x + y`
+  :check                      Checks the assignment against all constraints
+  :list                       Lists the constraints and the assignment
+  :reset                      Removes all constraints and assignments
+  :help                       Prints this message
+  :quit                       Exits the REPL";
+
+/// Interactively build and check constraints
+#[derive(StructOpt, Debug)]
+pub struct Repl {}
+
+impl Command for Repl {
+    type Input = ();
+    type Output = ();
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, _: Context, _: Self::Input) -> Result<Self::Output> {
+        // Initialize error handler
+        let handler = Handler::default();
+        let mut session = ReplSession::new(&handler);
+
+        tracing::info!("Welcome to the conlang REPL. Enter `:help` for a list of commands.");
+
+        let mut stdin = io::stdin().lock();
+        loop {
+            // The prompt is part of reading the input, so it is printed even with `-q`.
+            print!("conlang> ");
+            io::stdout().flush().map_err(CliError::cli_io_error)?;
+
+            // Read the next line, stopping at the end of the input.
+            let mut line = String::new();
+            if stdin.read_line(&mut line).map_err(CliError::cli_io_error)? == 0 {
+                break;
+            }
+
+            match session.evaluate_line(line.trim()) {
+                Ok(ReplAction::Print(message)) => tracing::info!("{message}"),
+                Ok(ReplAction::Continue) => {}
+                Ok(ReplAction::Quit) => break,
+                Err(err) => eprintln!("{err}"),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// What the REPL should do after evaluating a line.
+#[derive(Debug, PartialEq)]
+enum ReplAction {
+    /// Print the message, and read the next line.
+    Print(String),
+    /// Read the next line.
+    Continue,
+    /// Exit the REPL.
+    Quit,
+}
+
+/// The state of a REPL session.
+struct ReplSession<'a> {
+    /// The handler is used for error and warning emissions.
+    handler: &'a Handler,
+    /// The constraints entered so far.
+    program: Program,
+    /// The source text of each constraint in `program`.
+    sources: Vec<String>,
+    /// The variable values entered so far.
+    assignment: Assignment,
+    /// The number of lines registered in the source map.
+    line_count: usize,
+}
+
+impl<'a> ReplSession<'a> {
+    /// Returns a new, empty REPL session.
+    fn new(handler: &'a Handler) -> Self {
+        Self {
+            handler,
+            program: Program::default(),
+            sources: Vec::new(),
+            assignment: Assignment::default(),
+            line_count: 0,
+        }
+    }
+
+    /// Evaluates a line of input.
+    fn evaluate_line(&mut self, line: &str) -> Result<ReplAction> {
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let message = match command {
+            "" => return Ok(ReplAction::Continue),
+            ":quit" | ":q" => return Ok(ReplAction::Quit),
+            ":help" => HELP.to_string(),
+            ":eval" => self.evaluate(rest)?,
+            ":check" => self.check()?,
+            ":list" => self.list(),
+            ":reset" => {
+                // The sources entered before stay in the source map, but the numbering of new ones starts over.
+                *self = Self::new(self.handler);
+                return Ok(ReplAction::Continue);
+            }
+            _ if command.starts_with(':') => {
+                format!("Unknown command `{command}`. Enter `:help` for a list of commands.")
+            }
+            _ if line.starts_with('(') => self.add_constraint(line)?,
+            _ => self.assign(line)?,
+        };
+        Ok(ReplAction::Print(message))
+    }
+
+    /// Registers `source` in the source map, so that diagnostics can point into it.
+    fn register_source(&mut self, source: &str) -> (String, BytePos) {
+        self.line_count += 1;
+        let name = FileName::Custom(format!("repl#{}", self.line_count));
+        let source_file = with_session_globals(|s| s.source_map.new_source(source, name));
+        (source_file.src.clone(), source_file.start_pos)
+    }
+
+    /// Parses a constraint and adds it to the program.
+    fn add_constraint(&mut self, line: &str) -> Result<String> {
+        let (source, start_pos) = self.register_source(line);
+        let constraint = conlang_parser::parse_constraint(self.handler, &source, start_pos)?;
+        self.program.constraints.push(constraint);
+        self.sources.push(source);
+        Ok(format!("Added constraint #{}.", self.program.constraints.len()))
+    }
+
+    /// Parses variable assignments and adds them to the assignment, replacing previous values.
+    fn assign(&mut self, line: &str) -> Result<String> {
+        let (source, start_pos) = self.register_source(line);
+        let assignment = conlang_parser::parse_input(self.handler, &source, start_pos)?;
+        let mut lines = Vec::with_capacity(assignment.map.len());
+        for (name, constant) in assignment.map {
+            lines.push(format!("{name} = {}", constant.value));
+            self.assignment.map.insert(name, constant);
+        }
+        self.assignment.spans.extend(assignment.spans);
+        Ok(lines.join("\n"))
+    }
+
+    /// Parses and evaluates a linear combination under the current assignment.
+    fn evaluate(&mut self, source: &str) -> Result<String> {
+        let (source, start_pos) = self.register_source(source);
+        let linear_combination = conlang_parser::parse_linear_combination(self.handler, &source, start_pos)?;
+        Ok(Evaluator::evaluate_linear_combination(&linear_combination, &Values::new(&self.assignment))?.to_string())
+    }
+
+    /// Checks the current assignment against all constraints.
+    /// Unsatisfied constraints are reported through the handler.
+    fn check(&self) -> Result<String> {
+        match Evaluator::check_assignment(self.handler, &self.program, &self.assignment, &EvaluationOptions::default())?
+        {
+            true => Ok(format!("All {} constraints are satisfied.", self.program.constraints.len())),
+            false => Ok("The program is not satisfied.".to_string()),
+        }
+    }
+
+    /// Returns the constraints and the assignment, one per line.
+    fn list(&self) -> String {
+        let constraints = self.sources.iter().enumerate().map(|(index, source)| format!("#{}: {source}", index + 1));
+        let assignment = self.assignment.map.iter().map(|(name, constant)| format!("{name} = {}", constant.value));
+        constraints.chain(assignment).collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conlang_span::session_globals::create_session_if_not_set_then;
+
+    /// Returns the message that evaluating the `line` prints.
+    fn print(session: &mut ReplSession, line: &str) -> String {
+        match session.evaluate_line(line).unwrap() {
+            ReplAction::Print(message) => message,
+            action => panic!("`{line}` does not print, but returns {action:?}"),
+        }
+    }
+
+    #[test]
+    fn test_evaluate_line() {
+        create_session_if_not_set_then(|_| {
+            let (handler, buf) = Handler::new_with_buf();
+            let mut session = ReplSession::new(&handler);
+
+            assert_eq!(print(&mut session, "(x) * (x) = (y);"), "Added constraint #1.");
+            assert_eq!(print(&mut session, "x = 3; y = 9;"), "x = 3field\ny = 9field");
+            assert_eq!(print(&mut session, ":eval 2 * x + y"), "15field");
+            assert_eq!(print(&mut session, ":check"), "All 1 constraints are satisfied.");
+            assert_eq!(print(&mut session, ":list"), "#1: (x) * (x) = (y);\nx = 3field\ny = 9field");
+            assert!(buf.extract_errs().into_inner().is_empty());
+
+            // A failing constraint is reported through the handler.
+            print(&mut session, "y = 10;");
+            assert_eq!(print(&mut session, ":check"), "The program is not satisfied.");
+            let errors: Vec<_> = buf.extract_errs().into_inner().iter().map(|err| err.message()).collect();
+            assert_eq!(errors, ["The constraint is not satisfied, since `3field * 3field != 10field`."]);
+
+            // Malformed lines and unassigned variables are errors, which leave the session as it was.
+            assert!(session.evaluate_line("(x) * = (y);").is_err());
+            assert!(session.evaluate_line(":eval z").is_err());
+            assert_eq!(session.program.constraints.len(), 1);
+
+            let unknown = "Unknown command `:frobnicate`. Enter `:help` for a list of commands.";
+            assert_eq!(print(&mut session, ":frobnicate x"), unknown);
+            assert_eq!(session.evaluate_line("").unwrap(), ReplAction::Continue);
+            assert_eq!(session.evaluate_line(":q").unwrap(), ReplAction::Quit);
+        });
+    }
+
+    #[test]
+    fn test_reset() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let mut session = ReplSession::new(&handler);
+            print(&mut session, "(x) * (x) = (y);");
+            print(&mut session, "x = 3;");
+
+            // Everything is cleared, and new sources are numbered from the start.
+            assert_eq!(session.evaluate_line(":reset").unwrap(), ReplAction::Continue);
+            assert!(session.program.constraints.is_empty() && session.sources.is_empty());
+            assert!(session.assignment.map.is_empty());
+            assert_eq!(session.line_count, 0);
+            assert_eq!(print(&mut session, ":list"), "");
+            assert_eq!(print(&mut session, "(x) * (1) = (x);"), "Added constraint #1.");
+        });
+    }
+}
@@ -0,0 +1,288 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Evaluation of many assignments against one program.
+//!
+//! The assignments are loaded one after the other, since loading registers them in the source map of the session.
+//! They are then evaluated in parallel, and the results are reported in the order of the inputs.
+//! The workers share the symbols of the session, so that their errors can name the variables.
+
+use conlang_ast::{Assignment, Program};
+use conlang_compiler::{EvaluationOptions, Evaluator};
+use conlang_errors::{
+    emitter::{BufferEmitter, Handler},
+    CliError,
+    ConlangError,
+    ConlangWarning,
+    Result,
+};
+use conlang_span::{
+    session_globals::{create_session_with_symbols_then, with_session_globals},
+    source_map::FileName,
+    Symbol,
+};
+
+use indexmap::IndexSet;
+use rayon::prelude::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Returns whether the input file at `path` is a JSON Lines file, with one assignment per line.
+pub(super) fn is_json_lines(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "jsonl")
+}
+
+/// An assignment to evaluate, and the messages emitted while loading it.
+struct BatchInput {
+    /// The name of the input in the summary.
+    name: String,
+    /// The assignment, if it could be loaded.
+    assignment: Option<Assignment>,
+    /// The errors emitted while loading the assignment.
+    errors: Vec<ConlangError>,
+    /// The warnings emitted while loading the assignment.
+    warnings: Vec<ConlangWarning>,
+}
+
+impl BatchInput {
+    /// Returns the input called `name` from the result of loading its assignment.
+    fn new(name: String, assignment: Result<Assignment>, buffer: BufferEmitter) -> Self {
+        let mut errors = buffer.extract_errs().into_inner();
+        let assignment = match assignment {
+            Ok(assignment) if errors.is_empty() => Some(assignment),
+            Ok(_) => None,
+            Err(err) => {
+                errors.push(err);
+                None
+            }
+        };
+        Self { name, assignment, errors, warnings: buffer.extract_warnings().into_inner() }
+    }
+}
+
+/// Evaluates every assignment in the files at `input_paths` against the `program`,
+/// reporting the diagnostics through the `handler` and a summary on the standard output.
+/// Inputs that are not among the `variable_names` of the program are reported as unused.
+/// This function errors if any assignment does not satisfy the program.
+pub(super) fn evaluate(
+    handler: &Handler,
+    program: &Program,
+    variable_names: &IndexSet<Symbol>,
+    input_paths: &[PathBuf],
+    options: &EvaluationOptions,
+) -> Result<()> {
+    let mut inputs = Vec::new();
+    for path in input_paths {
+        match is_json_lines(path) {
+            true => load_json_lines(variable_names, path, &mut inputs)?,
+            false => inputs.push(load_input_file(variable_names, path)?),
+        }
+    }
+
+    let symbol_interner = with_session_globals(|s| s.symbol_interner.clone());
+    let results: Vec<_> = inputs
+        .par_iter()
+        .map(|input| {
+            create_session_with_symbols_then(symbol_interner.clone(), |_| {
+                input
+                    .assignment
+                    .as_ref()
+                    .map(|assignment| Evaluator::unsatisfied_constraints(program, assignment, options))
+            })
+        })
+        .collect();
+
+    let total = inputs.len();
+    let mut failed = 0;
+    for (input, result) in inputs.into_iter().zip(results) {
+        input.warnings.into_iter().for_each(|warning| handler.emit_warning(warning));
+        input.errors.into_iter().for_each(|err| handler.emit_err(err));
+
+        match result {
+            Some(Ok(unsatisfied)) if unsatisfied.is_empty() => println!("{}: satisfied", input.name),
+            Some(Ok(unsatisfied)) => {
+                failed += 1;
+                // Only the first unsatisfied constraint is reported in full, to keep the output readable.
+                handler.emit_err(unsatisfied[0].to_error());
+                let indices: Vec<_> =
+                    unsatisfied.iter().map(|constraint| format!("#{}", constraint.index + 1)).collect();
+                println!("{}: not satisfied (constraints {})", input.name, indices.join(", "));
+            }
+            Some(Err(err)) => {
+                failed += 1;
+                handler.emit_err(err);
+                println!("{}: error", input.name);
+            }
+            None => {
+                failed += 1;
+                println!("{}: error", input.name);
+            }
+        }
+    }
+
+    println!("\n{} of {} assignments satisfied the program.", total - failed, total);
+
+    match failed {
+        0 => Ok(()),
+        failed => Err(CliError::batch_evaluation_failed(failed, total).into()),
+    }
+}
+
+/// Loads the `.in` or `.json` file at `path`, warning about inputs that are not among the `variable_names`.
+fn load_input_file(variable_names: &IndexSet<Symbol>, path: &Path) -> Result<BatchInput> {
+    let string = fs::read_to_string(path).map_err(CliError::cli_io_error)?;
+
+    let (handler, buffer) = Handler::new_with_buf();
+    let assignment = match path.extension().is_some_and(|ext| ext == "json") {
+        true => Assignment::from_json_string(&string),
+        false => {
+            let input_sf =
+                with_session_globals(|s| s.source_map.new_source(&string, FileName::Real(path.to_path_buf())));
+            conlang_parser::parse_input(&handler, &input_sf.src, input_sf.start_pos)
+        }
+    };
+    if let Ok(assignment) = &assignment {
+        Evaluator::check_unused_inputs(&handler, variable_names, assignment);
+    }
+
+    Ok(BatchInput::new(path.display().to_string(), assignment, buffer))
+}
+
+/// Loads each non-empty line of the JSON Lines file at `path` as an assignment,
+/// warning about inputs that are not among the `variable_names`.
+fn load_json_lines(variable_names: &IndexSet<Symbol>, path: &Path, inputs: &mut Vec<BatchInput>) -> Result<()> {
+    let string = fs::read_to_string(path).map_err(CliError::cli_io_error)?;
+
+    for (index, line) in string.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let name = format!("{}:{}", path.display(), index + 1);
+        let assignment = serde_json::from_str(line)
+            .map_err(|e| CliError::invalid_json_line(&name, e).into())
+            .and_then(|value| Assignment::from_json(&value));
+
+        let (handler, buffer) = Handler::new_with_buf();
+        if let Ok(assignment) = &assignment {
+            Evaluator::check_unused_inputs(&handler, variable_names, assignment);
+        }
+        inputs.push(BatchInput::new(name, assignment, buffer));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conlang_span::session_globals::create_session_if_not_set_then;
+    use test_dir::{DirBuilder, FileType, TestDir};
+
+    /// Returns a directory with the given files.
+    fn files(files: &[(&str, &str)]) -> TestDir {
+        let dir = files.iter().fold(TestDir::temp(), |dir, (name, _)| dir.create(name, FileType::EmptyFile));
+        for (name, contents) in files {
+            fs::write(dir.path(name), contents).unwrap();
+        }
+        dir
+    }
+
+    /// Returns the program `(x) * (x) = (y);`.
+    fn program() -> Program {
+        conlang_parser::parse_program(&Handler::default(), "(x) * (x) = (y);", Default::default()).unwrap()
+    }
+
+    #[test]
+    fn test_load_json_lines() {
+        let lines = "{\"x\": \"3\", \"y\": \"9\"}\n\n{\"x\": \"3\",\n{\"x\": \"three\"}\n{\"x\": \"3\", \"z\": 1}\n";
+        let dir = files(&[("inputs.jsonl", lines)]);
+        let path = dir.path("inputs.jsonl");
+
+        create_session_if_not_set_then(|_| {
+            let variable_names = program().variable_names();
+            let mut inputs = Vec::new();
+            load_json_lines(&variable_names, &path, &mut inputs).unwrap();
+
+            // Empty lines are skipped, and the inputs are named after their line.
+            let names: Vec<_> = inputs.iter().map(|input| input.name.clone()).collect();
+            let expected: Vec<_> = [1, 3, 4, 5].iter().map(|line| format!("{}:{line}", path.display())).collect();
+            assert_eq!(names, expected);
+
+            // A line that is not JSON, or not an assignment, is an error of its own input.
+            assert!(inputs[0].assignment.is_some() && inputs[0].errors.is_empty());
+            assert!(inputs[1].assignment.is_none());
+            assert!(inputs[1].errors[0].message().starts_with(&format!("Failed to parse `{}` as JSON", names[1])));
+            assert!(inputs[2].assignment.is_none() && inputs[2].errors.len() == 1);
+
+            // Each line is checked for inputs that the program does not use.
+            assert!(inputs[0].warnings.is_empty());
+            assert!(inputs[3].assignment.is_some() && inputs[3].errors.is_empty());
+            let warnings: Vec<_> = inputs[3].warnings.iter().map(|warning| warning.message().to_string()).collect();
+            assert_eq!(warnings, ["The input variable `z` is not used by the program."]);
+
+            assert!(load_json_lines(&variable_names, &dir.path("missing.jsonl"), &mut inputs).is_err());
+        });
+    }
+
+    #[test]
+    fn test_load_input_file() {
+        let dir = files(&[
+            ("unused.in", "x = 3;\ny = 9;\nz = 1;\n"),
+            ("malformed.in", "x = ;\n"),
+            ("input.json", "{\"x\": \"3\", \"y\": 9}"),
+        ]);
+
+        create_session_if_not_set_then(|_| {
+            let program = program();
+
+            let input = load_input_file(&program.variable_names(), &dir.path("unused.in")).unwrap();
+            assert!(input.assignment.is_some() && input.errors.is_empty());
+            let warnings: Vec<_> = input.warnings.iter().map(|warning| warning.message().to_string()).collect();
+            assert_eq!(warnings, ["The input variable `z` is not used by the program."]);
+
+            let input = load_input_file(&program.variable_names(), &dir.path("malformed.in")).unwrap();
+            assert!(input.assignment.is_none() && !input.errors.is_empty());
+
+            let input = load_input_file(&program.variable_names(), &dir.path("input.json")).unwrap();
+            assert!(input.assignment.is_some() && input.errors.is_empty() && input.warnings.is_empty());
+
+            // A missing file fails the whole batch, rather than one of its inputs.
+            assert!(load_input_file(&program.variable_names(), &dir.path("missing.in")).is_err());
+        });
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let dir = files(&[
+            ("satisfied.in", "x = 3;\ny = 9;\n"),
+            ("inputs.jsonl", "{\"x\": \"2\", \"y\": \"4\"}\n{\"x\": \"2\", \"y\": \"5\"}\nnot json\n"),
+        ]);
+
+        create_session_if_not_set_then(|_| {
+            let program = program();
+            let (handler, buffer) = Handler::new_with_buf();
+            let paths = [dir.path("satisfied.in"), dir.path("inputs.jsonl")];
+
+            let (variable_names, options) = (program.variable_names(), EvaluationOptions::default());
+            let err = evaluate(&handler, &program, &variable_names, &paths, &options).unwrap_err();
+            assert_eq!(err.message(), "2 of 4 assignments did not satisfy the program.");
+            // The unsatisfied assignment and the malformed line are reported.
+            assert_eq!(buffer.extract_errs().into_inner().len(), 2);
+
+            let (handler, _) = Handler::new_with_buf();
+            assert!(evaluate(&handler, &program, &variable_names, &paths[..1], &options).is_ok());
+        });
+    }
+}
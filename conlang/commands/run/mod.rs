@@ -0,0 +1,418 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+mod batch;
+
+use crate::{
+    commands::{BuildOptions, Command},
+    context::Context,
+};
+
+use conlang_compiler::{Compiler, CompilerOptions, EvaluationOptions};
+use conlang_errors::{emitter::Handler, CliError, Result};
+use conlang_package::Package;
+use conlang_span::session_globals::create_session_then;
+
+use clap::StructOpt;
+use console::Term;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+use walkdir::WalkDir;
+
+/// How often the watched files are checked for changes.
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Build, Prove and Run Conlang program with inputs
+#[derive(StructOpt, Debug)]
+pub struct Run {
+    #[structopt(
+        name = "PATH",
+        requires = "INPUT",
+        help = "The path to the `.conlang` file, or to a `.json` file with a serialized program. \
+                Defaults to the entry file of the project."
+    )]
+    program_path: Option<PathBuf>,
+
+    #[structopt(
+        name = "INPUT",
+        help = "A `.in` or `.json` file, a directory or glob of `.in` files, or a `.jsonl` file with one assignment \
+                per line. \
+                Defaults to the input files of the project."
+    )]
+    input_path: Option<PathBuf>,
+
+    #[structopt(long, help = "Re-run whenever the program or input file changes.")]
+    watch: bool,
+
+    #[structopt(
+        long,
+        help = "Evaluate the constraints while parsing the program, instead of compiling it first. \
+                For very large programs without imports."
+    )]
+    stream: bool,
+
+    #[structopt(long, help = "Stop evaluating an input at its first unsatisfied constraint.")]
+    fail_fast: bool,
+
+    #[structopt(flatten)]
+    options: BuildOptions,
+}
+
+/// The program and input files to run.
+pub struct RunFiles {
+    /// The path to the program.
+    program_path: PathBuf,
+    /// The input file, directory, or glob, or `None` for the input files of the project.
+    /// It is resolved again on each run, so that a watched directory or glob picks up new files.
+    input_path: Option<PathBuf>,
+    /// Whether to evaluate the constraints while parsing the program, without compiling it.
+    stream: bool,
+    /// The options to compile the program with.
+    options: CompilerOptions,
+    /// The options to evaluate the inputs with.
+    evaluation_options: EvaluationOptions,
+    /// The project whose dependencies the imports of the program are resolved against, if there is one.
+    package: Option<Package>,
+}
+
+impl Command for Run {
+    type Input = RunFiles;
+    type Output = ();
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        let files = match (&self.program_path, &self.input_path) {
+            (Some(program_path), Some(input_path)) => RunFiles {
+                program_path: program_path.clone(),
+                input_path: Some(input_path.clone()),
+                stream: self.stream,
+                options: self.options.clone().into(),
+                evaluation_options: self.evaluation_options(),
+                package: context.open_package().ok(),
+            },
+            // Without arguments, run the program of the project with each of its inputs.
+            _ => {
+                let package = context.open_package()?;
+                RunFiles {
+                    program_path: package.entry_path(),
+                    input_path: None,
+                    stream: self.stream,
+                    options: self.options.clone().into(),
+                    evaluation_options: self.evaluation_options(),
+                    package: Some(package),
+                }
+            }
+        };
+        // Report missing inputs before the first run.
+        files.input_paths()?;
+        Ok(files)
+    }
+
+    fn apply(self, _: Context, files: Self::Input) -> Result<Self::Output> {
+        if !self.watch {
+            return files.run(&mut Vec::new());
+        }
+
+        let mut watcher = Watcher::default();
+        loop {
+            if watcher.changed(&files) {
+                Term::stdout().clear_screen().map_err(CliError::cli_io_error)?;
+
+                // Each run gets a fresh source map, so that byte positions do not grow across reloads.
+                watcher.import_paths.clear();
+                create_session_then(|_| {
+                    if let Err(err) = files.run(&mut watcher.import_paths) {
+                        Handler::default().emit_err(err);
+                    }
+                });
+
+                tracing::info!("\nWatching for changes...");
+            }
+            thread::sleep(WATCH_INTERVAL);
+        }
+    }
+}
+
+impl Run {
+    /// Returns the options to evaluate the inputs with.
+    fn evaluation_options(&self) -> EvaluationOptions {
+        EvaluationOptions { short_circuit: self.fail_fast, ..Default::default() }
+    }
+}
+
+impl RunFiles {
+    /// Returns the paths to the input files.
+    fn input_paths(&self) -> Result<Vec<PathBuf>> {
+        match (&self.input_path, &self.package) {
+            (Some(input_path), _) => find_input_files(input_path),
+            (None, Some(package)) => Ok(package.input_paths()),
+            (None, None) => Ok(Vec::new()),
+        }
+    }
+
+    /// Compiles the program and checks that each input satisfies it.
+    /// The paths of the programs it imports are added to `import_paths`, even if it fails to compile.
+    fn run(&self, import_paths: &mut Vec<PathBuf>) -> Result<()> {
+        // Initialize error handler
+        let handler = Handler::default();
+        let input_paths = self.input_paths()?;
+
+        let mut compiler = Compiler::new(&handler, &self.program_path)
+            .with_compiler_options(self.options.clone())
+            .with_evaluation_options(self.evaluation_options.clone());
+
+        // A streamed program is parsed again for each input, so that it is never held as a whole.
+        if self.stream {
+            if let Some(path) = input_paths.iter().find(|path| batch::is_json_lines(path)) {
+                return Err(CliError::cannot_stream_json_lines(path.display()).into());
+            }
+            for input_path in input_paths.iter() {
+                match compiler.stream_input(input_path)? {
+                    true => println!("The program is satisfied."),
+                    false => println!("The program is not satisfied."),
+                }
+            }
+            return Ok(());
+        }
+
+        // Compile the program.
+        if let Some(package) = &self.package {
+            compiler = compiler.with_import_resolver(|importer, name| package.resolve_import(importer, name));
+        }
+        let result = compiler.compile();
+        import_paths.extend(compiler.import_paths().iter().cloned());
+        result?;
+
+        // Several inputs are evaluated as a batch, with a summary of the results.
+        if input_paths.len() > 1 || input_paths.iter().any(|path| batch::is_json_lines(path)) {
+            return batch::evaluate(
+                &handler,
+                compiler.program(),
+                compiler.variable_names(),
+                &input_paths,
+                &self.evaluation_options,
+            );
+        }
+
+        // Evaluate the inputs.
+        for input_path in input_paths.iter() {
+            match compiler.evaluate_input(input_path)? {
+                true => println!("The program is satisfied."),
+                false => println!("The program is not satisfied."),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Watches the program, its imports and its input files, to run them again whenever they change.
+#[derive(Default)]
+struct Watcher {
+    /// The watched files and their modification times when they were last checked, or `None` before the first check.
+    /// A file that cannot be read has no modification time.
+    last_modified: Option<Vec<(PathBuf, Option<SystemTime>)>>,
+    /// The paths of the programs that the program imported when it last ran.
+    import_paths: Vec<PathBuf>,
+}
+
+impl Watcher {
+    /// Returns whether any of the `files` or imported programs was modified, created, or removed since the last check.
+    /// The input files are resolved again, so that a file added to a watched directory or glob is a change.
+    /// The files have always changed at the first check, so that they are run right away.
+    fn changed(&mut self, files: &RunFiles) -> bool {
+        let modified = std::iter::once(files.program_path.clone())
+            .chain(self.import_paths.iter().cloned())
+            .chain(files.input_paths().unwrap_or_default())
+            .map(|path| {
+                let modified = fs::metadata(&path).and_then(|metadata| metadata.modified()).ok();
+                (path, modified)
+            })
+            .collect();
+        let changed = self.last_modified.as_ref() != Some(&modified);
+        self.last_modified = Some(modified);
+        changed
+    }
+}
+
+/// Returns the input files at `path`, which is a file, a directory that is searched recursively for `.in` files,
+/// or a glob pattern.
+fn find_input_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = Vec::new();
+    if path.is_dir() {
+        for entry in WalkDir::new(path).sort_by_file_name() {
+            let entry = entry.map_err(CliError::cli_io_error)?;
+            if entry.file_type().is_file() && entry.path().extension().is_some_and(|ext| ext == "in") {
+                files.push(entry.into_path());
+            }
+        }
+    } else {
+        let pattern = path.to_string_lossy();
+        for entry in glob::glob(&pattern).map_err(|e| CliError::invalid_glob_pattern(&pattern, e))? {
+            files.push(entry.map_err(CliError::cli_io_error)?);
+        }
+    }
+
+    match files.is_empty() {
+        true => Err(CliError::no_input_files(path.display()).into()),
+        false => Ok(files),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, FileType, TestDir};
+
+    /// Returns the files to run the program at `program_path` with the inputs at `input_path`.
+    fn run_files(program_path: PathBuf, input_path: PathBuf) -> RunFiles {
+        RunFiles {
+            program_path,
+            input_path: Some(input_path),
+            stream: false,
+            options: Default::default(),
+            evaluation_options: Default::default(),
+            package: None,
+        }
+    }
+
+    /// Sets the modification time of the file at `path` to `seconds` after the epoch.
+    fn touch(path: &Path, seconds: u64) {
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds)).unwrap();
+    }
+
+    #[test]
+    fn test_watcher() {
+        let dir = TestDir::temp().create("main.conlang", FileType::EmptyFile).create("main.in", FileType::EmptyFile);
+        let (program_path, input_path) = (dir.path("main.conlang"), dir.path("main.in"));
+        touch(&program_path, 1);
+        touch(&input_path, 1);
+        let files = run_files(program_path.clone(), input_path.clone());
+
+        let mut watcher = Watcher::default();
+        assert!(watcher.changed(&files), "the files are run at the first check");
+        assert!(!watcher.changed(&files));
+
+        // A modified program or input is run again, but only once.
+        touch(&program_path, 2);
+        assert!(watcher.changed(&files));
+        assert!(!watcher.changed(&files));
+        touch(&input_path, 2);
+        assert!(watcher.changed(&files));
+        assert!(!watcher.changed(&files));
+
+        // A removed file is a change, and so is creating it again.
+        fs::remove_file(&input_path).unwrap();
+        assert!(watcher.changed(&files));
+        assert!(!watcher.changed(&files));
+        fs::write(&input_path, "x = 1;").unwrap();
+        touch(&input_path, 2);
+        assert!(watcher.changed(&files));
+    }
+
+    #[test]
+    fn test_watcher_new_inputs() {
+        let dir = TestDir::temp()
+            .create("main.conlang", FileType::EmptyFile)
+            .create("inputs", FileType::Dir)
+            .create("inputs/a.in", FileType::EmptyFile);
+
+        // A file added to a watched directory is a change.
+        let files = run_files(dir.path("main.conlang"), dir.path("inputs"));
+        let mut watcher = Watcher::default();
+        assert!(watcher.changed(&files));
+        assert!(!watcher.changed(&files));
+        fs::write(dir.path("inputs/b.in"), "x = 1;").unwrap();
+        assert!(watcher.changed(&files));
+        assert!(!watcher.changed(&files));
+
+        // So is a file matching a watched glob, but not a file that does not match it.
+        let files = run_files(dir.path("main.conlang"), dir.path("inputs/*.in"));
+        let mut watcher = Watcher::default();
+        assert!(watcher.changed(&files));
+        fs::write(dir.path("inputs/notes.txt"), "").unwrap();
+        assert!(!watcher.changed(&files));
+        fs::write(dir.path("inputs/c.in"), "x = 1;").unwrap();
+        assert!(watcher.changed(&files));
+    }
+
+    #[test]
+    fn test_watcher_imports() {
+        let dir = TestDir::temp();
+        let mut package = Package::initialize("main", &dir.path("main")).unwrap();
+        let square = Package::initialize("square", &dir.path("main/imports/square")).unwrap();
+        package.manifest.dependencies.insert("square".to_string(), "0.1.0".to_string());
+        fs::write(package.entry_path(), "import square as sq;\n").unwrap();
+
+        // The imported programs are recorded when the program runs.
+        let files = RunFiles {
+            program_path: package.entry_path(),
+            input_path: None,
+            stream: false,
+            options: Default::default(),
+            evaluation_options: Default::default(),
+            package: Some(package),
+        };
+        let mut watcher = Watcher::default();
+        create_session_then(|_| {
+            let _ = files.run(&mut watcher.import_paths);
+        });
+        assert_eq!(watcher.import_paths, [square.entry_path()]);
+
+        // A modified imported program is run again.
+        touch(&square.entry_path(), 1);
+        assert!(watcher.changed(&files));
+        assert!(!watcher.changed(&files));
+        touch(&square.entry_path(), 2);
+        assert!(watcher.changed(&files));
+    }
+
+    #[test]
+    fn test_find_input_files() {
+        let dir = TestDir::temp()
+            .create("inputs", FileType::Dir)
+            .create("inputs/a.in", FileType::EmptyFile)
+            .create("inputs/b.jsonl", FileType::EmptyFile)
+            .create("inputs/nested", FileType::Dir)
+            .create("inputs/nested/c.in", FileType::EmptyFile)
+            .create("inputs/notes.txt", FileType::EmptyFile);
+
+        // A directory is searched recursively for `.in` files.
+        let files = find_input_files(&dir.path("inputs")).unwrap();
+        assert_eq!(files, [dir.path("inputs/a.in"), dir.path("inputs/nested/c.in")]);
+
+        // A glob matches any file, and a file is used as it is.
+        let files = find_input_files(&dir.path("inputs/*.*l")).unwrap();
+        assert_eq!(files, [dir.path("inputs/b.jsonl")]);
+        let files = find_input_files(&dir.path("inputs/notes.txt")).unwrap();
+        assert_eq!(files, [dir.path("inputs/notes.txt")]);
+
+        let err = find_input_files(&dir.path("inputs/*.json")).unwrap_err();
+        assert!(err.message().starts_with("No input files were found"));
+        let err = find_input_files(&dir.path("inputs/[")).unwrap_err();
+        assert!(err.message().starts_with("Invalid input pattern"));
+        let err = find_input_files(&dir.path("nested")).unwrap_err();
+        assert!(err.message().starts_with("No input files were found"));
+    }
+}
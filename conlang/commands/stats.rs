@@ -0,0 +1,72 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    commands::{BuildOptions, Command},
+    context::Context,
+};
+
+use conlang_compiler::Compiler;
+use conlang_errors::{emitter::Handler, CliError, Result};
+
+use clap::StructOpt;
+use std::path::PathBuf;
+
+/// Report the size of a program's circuit
+#[derive(StructOpt, Debug)]
+pub struct Stats {
+    #[structopt(name = "PATH", help = "The path to the `.conlang` file. Defaults to the entry file of the project.")]
+    program_path: Option<PathBuf>,
+
+    #[structopt(long, help = "Print the statistics as JSON.")]
+    json: bool,
+
+    #[structopt(flatten)]
+    options: BuildOptions,
+}
+
+impl Command for Stats {
+    type Input = PathBuf;
+    type Output = ();
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        match &self.program_path {
+            Some(program_path) => Ok(program_path.clone()),
+            None => Ok(context.open_package()?.entry_path()),
+        }
+    }
+
+    fn apply(self, context: Context, program_path: Self::Input) -> Result<Self::Output> {
+        // Initialize error handler
+        let handler = Handler::default();
+
+        // Compile the program.
+        let mut compiler = Compiler::new(&handler, &program_path).with_compiler_options(self.options.into());
+        // Imports are resolved against the dependencies of the project, if there is one.
+        if let Ok(package) = context.open_package() {
+            compiler = compiler.with_import_resolver(move |importer, name| package.resolve_import(importer, name));
+        }
+        compiler.compile()?;
+
+        let statistics = compiler.statistics_pass();
+        match self.json {
+            true => println!("{}", serde_json::to_string_pretty(&statistics).map_err(CliError::cli_io_error)?),
+            false => print!("{statistics}"),
+        }
+
+        Ok(())
+    }
+}
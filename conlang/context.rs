@@ -0,0 +1,58 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use conlang_errors::{CliError, Result};
+use conlang_package::{Package, Registry};
+
+use std::{env, path::PathBuf};
+
+/// The context a command runs in, i.e., the project it operates on.
+#[derive(Clone, Debug)]
+pub struct Context {
+    /// The path to the project root, if it was given with `--path`.
+    pub path: Option<PathBuf>,
+    /// The location of the package registry, if it was given with `APM_URL`.
+    pub api: Option<String>,
+}
+
+impl Context {
+    /// Returns a new context for the project at `path`, or the current directory if there is none,
+    /// and the registry at `api`, or the default registry if there is none.
+    pub fn new(path: Option<PathBuf>, api: Option<String>) -> Self {
+        Self { path, api }
+    }
+
+    /// Returns the project root directory.
+    pub fn dir(&self) -> Result<PathBuf> {
+        match &self.path {
+            Some(path) => Ok(path.clone()),
+            None => Ok(env::current_dir().map_err(CliError::cli_io_error)?),
+        }
+    }
+
+    /// Opens the project in the project root directory.
+    pub fn open_package(&self) -> Result<Package> {
+        Package::open(&self.dir()?)
+    }
+
+    /// Returns the package registry, which defaults to `~/.conlang/registry`.
+    pub fn registry(&self) -> Result<Registry> {
+        match &self.api {
+            Some(location) => Registry::from_location(location),
+            None => Ok(Registry::new(dirs::home_dir().unwrap_or_default().join(".conlang").join("registry"))),
+        }
+    }
+}
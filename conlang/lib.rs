@@ -18,3 +18,5 @@
 #![doc = include_str!("../README.md")]
 
 pub mod commands;
+pub mod context;
+pub mod logger;
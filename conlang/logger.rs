@@ -0,0 +1,137 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use conlang_errors::{CliError, Result};
+
+use std::io::IsTerminal;
+use tracing::{Level, Subscriber};
+use tracing_subscriber::{
+    fmt::{
+        format::{format, FmtSpan},
+        writer::{BoxMakeWriter, MakeWriterExt},
+        MakeWriter,
+    },
+    util::SubscriberInitExt,
+};
+
+/// Initializes the logger, which prints messages to the standard output, and warnings and errors
+/// to the standard error. With `stderr_only`, everything is printed to the standard error, e.g., because
+/// the standard output carries the messages of the language server.
+///
+/// At verbosity `0`, only warnings and errors are printed. At `1`, messages are printed as well.
+/// At `2`, debug information is printed too, including the time each compiler stage takes.
+/// The results of a command, e.g., whether a program is satisfied, and the prompt of the REPL are printed to the
+/// standard output directly, and the errors that end a command to the standard error, at any verbosity.
+pub fn init_logger(verbosity: usize, stderr_only: bool) -> Result<()> {
+    let writer = writer(std::io::stdout, std::io::stderr, stderr_only);
+    let ansi = match stderr_only {
+        true => std::io::stderr().is_terminal(),
+        false => std::io::stdout().is_terminal(),
+    };
+
+    subscriber(verbosity, writer, ansi).try_init().map_err(|e| CliError::failed_to_initialize_logger(e).into())
+}
+
+/// Returns the writer of the logger, which writes warnings and errors to `stderr` and other messages to `stdout`,
+/// or everything to `stderr` with `stderr_only`.
+fn writer<O, E>(stdout: O, stderr: E, stderr_only: bool) -> BoxMakeWriter
+where
+    O: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    E: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    match stderr_only {
+        true => BoxMakeWriter::new(stderr),
+        false => BoxMakeWriter::new(stderr.with_max_level(Level::WARN).or_else(stdout)),
+    }
+}
+
+/// Returns the subscriber of the logger, which prints to the `writer` at the given `verbosity`.
+fn subscriber(verbosity: usize, writer: BoxMakeWriter, ansi: bool) -> impl Subscriber + Send + Sync {
+    let level = match verbosity {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        _ => Level::DEBUG,
+    };
+
+    tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_max_level(level)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(ansi)
+        // Timestamps are omitted from events only, so that closed spans still report their timings.
+        .event_format(format().without_time().with_level(verbosity > 1).with_target(false))
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    /// An in-memory output of the logger.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Returns the standard output and standard error of logging a message at each level.
+    fn log(verbosity: usize, stderr_only: bool) -> (String, String) {
+        let (stdout, stderr) = (Buffer::default(), Buffer::default());
+        let make_writer = |buffer: &Buffer| {
+            let buffer = buffer.clone();
+            move || buffer.clone()
+        };
+        let writer = writer(make_writer(&stdout), make_writer(&stderr), stderr_only);
+
+        tracing::subscriber::with_default(subscriber(verbosity, writer, false), || {
+            tracing::debug!("debug");
+            tracing::info!("info");
+            tracing::warn!("warning");
+            tracing::error!("error");
+        });
+
+        let contents = |buffer: Buffer| String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        (contents(stdout), contents(stderr))
+    }
+
+    #[test]
+    fn test_logger() {
+        assert_eq!(log(1, false), ("info\n".to_string(), "warning\nerror\n".to_string()));
+        // With `-q`, only warnings and errors are printed.
+        assert_eq!(log(0, false), (String::new(), "warning\nerror\n".to_string()));
+        // With `-d`, debug messages are printed too, with their level.
+        let (stdout, _) = log(2, false);
+        assert!(stdout.contains("DEBUG debug") && stdout.contains("INFO info"), "{stdout}");
+    }
+
+    #[test]
+    fn test_logger_stderr_only() {
+        // The language server keeps the standard output for its messages.
+        assert_eq!(log(1, true), (String::new(), "info\nwarning\nerror\n".to_string()));
+        assert_eq!(log(0, true), (String::new(), "warning\nerror\n".to_string()));
+    }
+}
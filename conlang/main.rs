@@ -15,8 +15,10 @@
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
 pub mod commands;
+pub mod context;
+pub mod logger;
 
-use crate::commands::*;
+use crate::{commands::*, context::Context};
 use conlang_errors::Result;
 use conlang_span::session_globals::create_session_if_not_set_then;
 
@@ -30,13 +32,13 @@ pub struct CLI {
     #[structopt(short, global = true, help = "Print additional information for debugging")]
     debug: bool,
 
-    #[structopt(short, global = true, help = "Suppress CLI output")]
+    #[structopt(short, global = true, help = "Suppress log messages, except for warnings and errors")]
     quiet: bool,
 
     #[structopt(subcommand)]
     command: Commands,
 
-    #[structopt(help = "Custom Aleo PM backend URL", env = "APM_URL")]
+    #[structopt(long, global = true, help = "Custom package registry directory or `file://` URL", env = "APM_URL")]
     api: Option<String>,
 
     #[structopt(long, global = true, help = "Optional path to Conlang program root folder", parse(from_os_str))]
@@ -46,6 +48,51 @@ pub struct CLI {
 ///Conlang compiler and package manager
 #[derive(StructOpt, Debug)]
 enum Commands {
+    #[structopt(about = "Create a new project")]
+    New {
+        #[structopt(flatten)]
+        command: New,
+    },
+    #[structopt(about = "Add a dependency from the package registry")]
+    Add {
+        #[structopt(flatten)]
+        command: Add,
+    },
+    #[structopt(about = "Publish the project to the package registry")]
+    Publish {
+        #[structopt(flatten)]
+        command: Publish,
+    },
+    #[structopt(about = "Compile the program of a project")]
+    Build {
+        #[structopt(flatten)]
+        command: Build,
+    },
+    #[structopt(about = "Check programs for errors without running them")]
+    Check {
+        #[structopt(flatten)]
+        command: Check,
+    },
+    #[structopt(about = "Export the compiled circuit of a program")]
+    Export {
+        #[structopt(flatten)]
+        command: Export,
+    },
+    #[structopt(about = "Report the size of a program's circuit")]
+    Stats {
+        #[structopt(flatten)]
+        command: Stats,
+    },
+    #[structopt(about = "Start a language server over stdio")]
+    Lsp {
+        #[structopt(flatten)]
+        command: Lsp,
+    },
+    #[structopt(about = "Interactively build and check constraints")]
+    Repl {
+        #[structopt(flatten)]
+        command: Repl,
+    },
     #[structopt(about = "Run a program with input variables")]
     Run {
         #[structopt(flatten)]
@@ -86,8 +133,29 @@ pub fn handle_error<T>(res: Result<T>) -> T {
 
 /// Run command with custom build arguments.
 pub fn run_with_args(cli: CLI) -> Result<()> {
+    let verbosity = if cli.quiet {
+        0
+    } else if cli.debug {
+        2
+    } else {
+        1
+    };
+    // The standard output of the language server carries its messages, so it cannot be logged to.
+    logger::init_logger(verbosity, matches!(cli.command, Commands::Lsp { .. }))?;
+
+    let context = Context::new(cli.path, cli.api);
+
     match cli.command {
-        Commands::Run { command } => command.try_execute(),
+        Commands::New { command } => command.try_execute(context),
+        Commands::Add { command } => command.try_execute(context),
+        Commands::Publish { command } => command.try_execute(context),
+        Commands::Build { command } => command.try_execute(context),
+        Commands::Check { command } => command.try_execute(context),
+        Commands::Export { command } => command.try_execute(context),
+        Commands::Stats { command } => command.try_execute(context),
+        Commands::Lsp { command } => command.try_execute(context),
+        Commands::Repl { command } => command.try_execute(context),
+        Commands::Run { command } => command.try_execute(context),
     }
 }
 
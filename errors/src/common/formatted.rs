@@ -36,8 +36,31 @@ use std::fmt;
 pub struct Formatted {
     /// The formatted error span information.
     pub span: Span,
+    /// Secondary spans, each with a message describing its relevance to the error.
+    pub labels: Vec<Label>,
     /// The backtrace to track where the Conlang error originated.
-    pub backtrace: Backtraced,
+    /// It is boxed so that results carrying a formatted error stay small.
+    pub backtrace: Box<Backtraced>,
+}
+
+/// A secondary span of a formatted error, rendered below the primary span.
+///     --> file.conlang: 1:1
+///      |
+///    1 | x = 1;
+///      | ^ first assigned here
+#[derive(Clone, Debug, Default, Hash, PartialEq)]
+pub struct Label {
+    /// The span the label points at.
+    pub span: Span,
+    /// The message rendered next to the underlined span.
+    pub message: String,
+}
+
+impl Label {
+    /// Creates a label pointing at `span` with the given `message`.
+    pub fn new<S: ToString>(span: Span, message: S) -> Self {
+        Self { span, message: message.to_string() }
+    }
 }
 
 impl Formatted {
@@ -58,7 +81,8 @@ impl Formatted {
     {
         Self {
             span,
-            backtrace: Backtraced::new_from_backtrace(
+            labels: Vec::new(),
+            backtrace: Box::new(Backtraced::new_from_backtrace(
                 message.to_string(),
                 help,
                 code,
@@ -66,10 +90,16 @@ impl Formatted {
                 type_,
                 error,
                 backtrace,
-            ),
+            )),
         }
     }
 
+    /// Attaches the secondary `labels` to the error.
+    pub fn with_labels(mut self, labels: Vec<Label>) -> Self {
+        self.labels = labels;
+        self
+    }
+
     /// Calls the backtraces error exit code.
     pub fn exit_code(&self) -> i32 {
         self.backtrace.exit_code()
@@ -86,36 +116,69 @@ impl Formatted {
     }
 }
 
-impl fmt::Display for Formatted {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let underline = |mut start: usize, mut end: usize| -> String {
-            if start > end {
-                std::mem::swap(&mut start, &mut end)
-            }
+/// Returns the underline for the columns `start` through `end`.
+fn underline(mut start: usize, mut end: usize) -> String {
+    if start > end {
+        std::mem::swap(&mut start, &mut end)
+    }
 
-            let mut underline = String::new();
+    let mut underline = String::new();
 
-            for _ in 0..start {
-                underline.push(' ');
-                end -= 1;
-            }
+    for _ in 0..start {
+        underline.push(' ');
+        end -= 1;
+    }
 
-            for _ in 0..end {
-                underline.push('^');
-            }
+    for _ in 0..end {
+        underline.push('^');
+    }
 
-            underline
-        };
+    underline
+}
 
-        let (loc, contents) = with_session_globals(|s| {
-            (
-                s.source_map.span_to_location(self.span).unwrap_or_else(SpanLocation::dummy),
-                s.source_map.line_contents_of_span(self.span).unwrap_or_else(|| "<contents unavailable>".to_owned()),
-            )
-        });
+/// Writes the source lines of `span`, preceded by a location `marker` and followed by an underline.
+/// The underline is suffixed by the `label` message if there is one.
+fn write_snippet(f: &mut fmt::Formatter, marker: &str, span: Span, label: Option<&str>) -> fmt::Result {
+    let (loc, contents) = with_session_globals(|s| {
+        (
+            s.source_map.span_to_location(span).unwrap_or_else(SpanLocation::dummy),
+            s.source_map.line_contents_of_span(span).unwrap_or_else(|| "<contents unavailable>".to_owned()),
+        )
+    });
+
+    let underlined = underline(loc.col_start, loc.col_stop);
+
+    write!(
+        f,
+        "\n{indent     }{marker} {path}:{line_start}:{start}\n\
+        {indent     } |\n",
+        indent = INDENT,
+        path = &loc.source_file.name,
+        line_start = loc.line_start,
+        start = loc.col_start,
+    )?;
+
+    for (line_no, line) in contents.lines().enumerate() {
+        writeln!(
+            f,
+            "{line_no:width$} | {text}",
+            width = INDENT.len(),
+            line_no = loc.line_start + line_no,
+            text = line,
+        )?;
+    }
 
-        let underlined = underline(loc.col_start, loc.col_stop);
+    write!(f, "{INDENT     } |{underlined}",)?;
+
+    if let Some(label) = label {
+        write!(f, " {label}")?;
+    }
 
+    Ok(())
+}
+
+impl fmt::Display for Formatted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let (kind, code) =
             if self.backtrace.error { ("Error", self.error_code()) } else { ("Warning", self.warning_code()) };
 
@@ -132,27 +195,19 @@ impl fmt::Display for Formatted {
             write!(f, "{message}")?;
         };
 
-        write!(
-            f,
-            "\n{indent     }--> {path}:{line_start}:{start}\n\
-            {indent     } |\n",
-            indent = INDENT,
-            path = &loc.source_file.name,
-            line_start = loc.line_start,
-            start = loc.col_start,
-        )?;
-
-        for (line_no, line) in contents.lines().enumerate() {
-            writeln!(
-                f,
-                "{line_no:width$} | {text}",
-                width = INDENT.len(),
-                line_no = loc.line_start + line_no,
-                text = line,
-            )?;
+        // Nodes that do not come from a source file, e.g., those loaded from JSON, have no snippet to show.
+        if !self.span.is_dummy() {
+            write_snippet(f, "-->", self.span, None)?;
         }
 
-        write!(f, "{INDENT     } |{underlined}",)?;
+        for label in &self.labels {
+            if label.span.is_dummy() {
+                write!(f, "\n{INDENT     } = note: {}", label.message)?;
+            } else {
+                write!(f, "\n{INDENT     } |")?;
+                write_snippet(f, ":::", label.span, Some(&label.message))?;
+            }
+        }
 
         if let Some(help) = &self.backtrace.help {
             write!(
@@ -190,3 +245,83 @@ impl std::error::Error for Formatted {
         &self.backtrace.message
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{CompilerError, InputError};
+    use conlang_span::{
+        session_globals::{create_session_if_not_set_then, with_session_globals},
+        source_map::FileName,
+        span::BytePos,
+        Span,
+    };
+
+    /// Registers the `source` under `name`, and returns a function that gives the span of the first occurrence of
+    /// a text in it.
+    fn register(name: &str, source: &'static str) -> impl Fn(&str) -> Span {
+        let file = with_session_globals(|s| s.source_map.new_source(source, FileName::Custom(name.to_string())));
+        move |text| {
+            let start = file.start_pos + BytePos(source.find(text).unwrap() as u32);
+            Span::new(start, start + BytePos(text.len() as u32))
+        }
+    }
+
+    #[test]
+    fn test_secondary_labels() {
+        // Renders the messages without colors.
+        std::env::set_var("LEO_TESTFRAMEWORK", "1");
+        create_session_if_not_set_then(|_| {
+            // The first assignment is labeled below the duplicate one.
+            let span = register("input", "x: 1;\ny: 2;\nx: 3;\n");
+            let error = InputError::duplicate_input_variable("x", span("x: 1"), span("x: 3"));
+            assert_eq!(
+                error.to_string(),
+                "Error [EINP0371000]: Variables in an input assignment must be unique.
+    --> input:3:1
+     |
+   3 | x: 3;
+     | ^^^^
+     |
+    ::: input:1:1
+     |
+   1 | x: 1;
+     | ^^^^ `x` is first assigned here"
+            );
+
+            // Each of `A`, `B` and `C` is labeled with its value, in order.
+            let span = register("program", "(x + 1) * (y) = (z);\n");
+            let error = CompilerError::constraint_not_satisfied(
+                "3",
+                span("x + 1"),
+                "2",
+                span("y"),
+                "5",
+                span("z"),
+                span("(x + 1) * (y) = (z);"),
+            );
+            assert_eq!(
+                error.to_string(),
+                "Error [ECMP0376002]: The constraint is not satisfied, since `3 * 2 != 5`.
+    --> program:1:1
+     |
+   1 | (x + 1) * (y) = (z);
+     | ^^^^^^^^^^^^^^^^^^^^
+     |
+    ::: program:1:2
+     |
+   1 | (x + 1) * (y) = (z);
+     |  ^^^^^ `A` evaluates to `3`
+     |
+    ::: program:1:12
+     |
+   1 | (x + 1) * (y) = (z);
+     |            ^ `B` evaluates to `2`
+     |
+    ::: program:1:18
+     |
+   1 | (x + 1) * (y) = (z);
+     |                  ^ `C` evaluates to `5`"
+            );
+        });
+    }
+}
@@ -17,8 +17,18 @@
 /// A macro that given an enum, exit code mask, error code string prefix,
 /// and error methods generated through a DSL creates and generates errors
 /// with a unique error code.
+///
+/// Formatted messages may additionally list `labels`, an expression of type `Vec<Label>`
+/// pointing at secondary spans, which are rendered below the primary span.
 #[macro_export]
 macro_rules! create_messages {
+    // Expands the optional labels of a message.
+    (@labels {}) => {
+        Vec::new()
+    };
+    (@labels {$labels:expr}) => {
+        $labels
+    };
     (@step $code:expr,) => {
         #[inline(always)]
         // Returns the number of unique exit codes that this error type can take on.
@@ -26,9 +36,9 @@ macro_rules! create_messages {
             $code
         }
     };
-    ($(#[$error_type_docs:meta])* $type_:ident, code_mask: $code_mask:expr, code_prefix: $code_prefix:expr, $($(#[$docs:meta])* @$formatted_or_backtraced_list:ident $names:ident { args: ($($arg_names:ident: $arg_types:ty$(,)?)*), msg: $messages:expr, help: $helps:expr, })*) => {
+    ($(#[$error_type_docs:meta])* $type_:ident, code_mask: $code_mask:expr, code_prefix: $code_prefix:expr, $($(#[$docs:meta])* @$formatted_or_backtraced_list:ident $names:ident { args: ($($arg_names:ident: $arg_types:ty$(,)?)*), msg: $messages:expr, help: $helps:expr, $(labels: $labels:expr,)? })*) => {
         #[allow(unused_imports)] // Allow unused for errors that only use formatted or backtraced errors.
-        use $crate::{Backtraced, Formatted, ConlangMessageCode};
+        use $crate::{Backtraced, Formatted, Label, ConlangMessageCode};
 
         #[allow(unused_imports)]
         use backtrace::Backtrace;
@@ -87,13 +97,31 @@ macro_rules! create_messages {
         }
 
 
+        impl $type_ {
+            /// Returns the message if it points at a span in the source code.
+            pub fn formatted(&self) -> Option<&Formatted> {
+                match self {
+                    Self::Formatted(formatted) => Some(formatted),
+                    Self::Backtraced(_) => None,
+                }
+            }
+
+            /// Returns the bare message, without its code, location, or help.
+            pub fn message(&self) -> &str {
+                match self {
+                    Self::Formatted(formatted) => &formatted.backtrace.message,
+                    Self::Backtraced(backtraced) => &backtraced.message,
+                }
+            }
+        }
+
         // Steps over the list of functions with an initial code of 0.
         impl $type_ {
-            create_messages!(@step 0i32, $(($(#[$docs])* $formatted_or_backtraced_list, $names($($arg_names: $arg_types,)*), $messages, $helps),)*);
+            create_messages!(@step 0i32, $(($(#[$docs])* $formatted_or_backtraced_list, $names($($arg_names: $arg_types,)*), $messages, $helps, {$($labels)?}),)*);
         }
     };
     // Matches the function if it is a formatted message.
-    (@step $code:expr, ($(#[$error_func_docs:meta])* formatted, $name:ident($($arg_names:ident: $arg_types:ty,)*), $message:expr, $help:expr), $(($(#[$docs:meta])* $formatted_or_backtraced_tail:ident, $names:ident($($tail_arg_names:ident: $tail_arg_types:ty,)*), $messages:expr, $helps:expr),)*) => {
+    (@step $code:expr, ($(#[$error_func_docs:meta])* formatted, $name:ident($($arg_names:ident: $arg_types:ty,)*), $message:expr, $help:expr, $labels:tt), $(($(#[$docs:meta])* $formatted_or_backtraced_tail:ident, $names:ident($($tail_arg_names:ident: $tail_arg_types:ty,)*), $messages:expr, $helps:expr, $tail_labels:tt),)*) => {
         // Formatted errors always takes a span.
         $(#[$error_func_docs])*
         // Expands additional arguments for the error defining function.
@@ -110,14 +138,15 @@ macro_rules! create_messages {
                     // Each function always generates its own backtrace for backtrace clarity to originate from the error function.
                     Backtrace::new(),
                 )
+                .with_labels(create_messages!(@labels $labels))
             )
         }
 
         // Steps the code value by one and calls on the rest of the functions.
-        create_messages!(@step $code + 1i32, $(($(#[$docs])* $formatted_or_backtraced_tail, $names($($tail_arg_names: $tail_arg_types,)*), $messages, $helps),)*);
+        create_messages!(@step $code + 1i32, $(($(#[$docs])* $formatted_or_backtraced_tail, $names($($tail_arg_names: $tail_arg_types,)*), $messages, $helps, $tail_labels),)*);
     };
     // matches the function if it is a backtraced message.
-    (@step $code:expr, ($(#[$error_func_docs:meta])* backtraced, $name:ident($($arg_names:ident: $arg_types:ty,)*), $message:expr, $help:expr), $(($(#[$docs:meta])* $formatted_or_backtraced_tail:ident, $names:ident($($tail_arg_names:ident: $tail_arg_types:ty,)*), $messages:expr, $helps:expr),)*) => {
+    (@step $code:expr, ($(#[$error_func_docs:meta])* backtraced, $name:ident($($arg_names:ident: $arg_types:ty,)*), $message:expr, $help:expr, $labels:tt), $(($(#[$docs:meta])* $formatted_or_backtraced_tail:ident, $names:ident($($tail_arg_names:ident: $tail_arg_types:ty,)*), $messages:expr, $helps:expr, $tail_labels:tt),)*) => {
         $(#[$error_func_docs])*
         // Expands additional arguments for the error defining function.
         pub fn $name($($arg_names: $arg_types,)*) -> Self {
@@ -136,6 +165,6 @@ macro_rules! create_messages {
         }
 
         // Steps the code value by one and calls on the rest of the functions.
-        create_messages!(@step $code + 1i32, $(($(#[$docs])* $formatted_or_backtraced_tail, $names($($tail_arg_names: $tail_arg_types,)*), $messages, $helps),)*);
+        create_messages!(@step $code + 1i32, $(($(#[$docs])* $formatted_or_backtraced_tail, $names($($tail_arg_names: $tail_arg_types,)*), $messages, $helps, $tail_labels),)*);
     };
 }
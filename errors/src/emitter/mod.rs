@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use super::ConlangError;
+use crate::{ConlangError, ConlangWarning};
 use core::{default::Default, fmt};
 use std::{cell::RefCell, rc::Rc};
 
@@ -25,6 +25,9 @@ pub trait Emitter {
 
     /// Tracks last emitted error.
     fn last_emitted_err_code(&self) -> Option<i32>;
+
+    /// Emit the warning.
+    fn emit_warning(&mut self, warning: ConlangWarning);
 }
 
 /// A trivial `Emitter` using the standard error.
@@ -42,6 +45,10 @@ impl Emitter for StderrEmitter {
     fn last_emitted_err_code(&self) -> Option<i32> {
         self.last_error_code
     }
+
+    fn emit_warning(&mut self, warning: ConlangWarning) {
+        eprintln!("{warning}");
+    }
 }
 
 /// A buffer of `T`s.
@@ -86,21 +93,28 @@ impl<T: fmt::Display> fmt::Display for Buffer<T> {
 
 /// A buffer of `ConlangError`s.
 pub type ErrBuffer = Buffer<ConlangError>;
+/// A buffer of `ConlangWarning`s.
+pub type WarningBuffer = Buffer<ConlangWarning>;
 
 /// An `Emitter` that collects into a list.
 #[derive(Default, Clone)]
-pub struct BufferEmitter(Rc<RefCell<ErrBuffer>>);
+pub struct BufferEmitter(Rc<RefCell<ErrBuffer>>, Rc<RefCell<WarningBuffer>>);
 
 impl BufferEmitter {
     /// Returns a new buffered emitter.
     pub fn new() -> Self {
-        BufferEmitter(<_>::default())
+        BufferEmitter(<_>::default(), <_>::default())
     }
 
     /// Extracts all the errors collected in this emitter.
     pub fn extract_errs(&self) -> ErrBuffer {
         self.0.take()
     }
+
+    /// Extracts all the warnings collected in this emitter.
+    pub fn extract_warnings(&self) -> WarningBuffer {
+        self.1.take()
+    }
 }
 
 impl Emitter for BufferEmitter {
@@ -112,6 +126,10 @@ impl Emitter for BufferEmitter {
         let temp = &*self.0.borrow();
         temp.last_entry().map(|entry| entry.exit_code())
     }
+
+    fn emit_warning(&mut self, warning: ConlangWarning) {
+        self.1.borrow_mut().push(warning);
+    }
 }
 
 /// Contains the actual data for `Handler`.
@@ -136,6 +154,12 @@ impl HandlerInner {
     fn last_emitted_err_code(&self) -> Option<i32> {
         self.emitter.last_emitted_err_code()
     }
+
+    /// Emit the warning `warning`.
+    fn emit_warning(&mut self, warning: ConlangWarning) {
+        self.warn_count = self.warn_count.saturating_add(1);
+        self.emitter.emit_warning(warning);
+    }
 }
 
 /// A handler deals with errors and other compiler output.
@@ -177,6 +201,11 @@ impl Handler {
         self.inner.borrow_mut().emit_err(err.into());
     }
 
+    /// Emit the warning `warning`.
+    pub fn emit_warning<W: Into<ConlangWarning>>(&self, warning: W) {
+        self.inner.borrow_mut().emit_warning(warning.into());
+    }
+
     /// Emits the error `err`.
     /// This will immediately abort compilation.
     pub fn fatal_err(&self, err: ConlangError) -> ! {
@@ -14,8 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::create_messages;
-use std::fmt::Debug;
+use std::{error::Error as ErrorArg, fmt::Debug};
 
 create_messages!(
     /// AstError enum that represents all the errors for the `conlang-ast` crate.
@@ -23,4 +22,43 @@ create_messages!(
     code_mask: 2000i32,
     code_prefix: "AST",
 
+    /// For when the AST fails to be represented as a JSON string.
+    @backtraced
+    failed_to_convert_ast_to_json_string {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to convert ast to a json string {error}"),
+        help: None,
+    }
+
+    /// For when the AST fails to create the AST JSON file.
+    @backtraced
+    failed_to_create_ast_json_file {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("failed to create ast json file `{path:?}` {error}"),
+        help: None,
+    }
+
+    /// For when the AST fails to write the AST JSON file.
+    @backtraced
+    failed_to_write_ast_to_json_file {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("failed to write ast to a json file `{path:?}` {error}"),
+        help: None,
+    }
+
+    /// For when a JSON string fails to be represented as an AST.
+    @backtraced
+    failed_to_read_json_string_to_ast {
+        args: (error: impl ErrorArg),
+        msg: format!("failed to convert json string to an ast {error}"),
+        help: None,
+    }
+
+    /// For when the AST fails to read the AST JSON file.
+    @backtraced
+    failed_to_read_json_file {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("failed to read json file `{path:?}` {error}"),
+        help: None,
+    }
 );
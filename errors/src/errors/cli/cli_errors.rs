@@ -14,8 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::create_messages;
-use std::{error::Error as ErrorArg, fmt::Debug};
+use std::{
+    error::Error as ErrorArg,
+    fmt::{Debug, Display},
+};
 
 create_messages!(
     /// CliError enum that represents all the errors for the  `conlang-lang` crate.
@@ -31,4 +33,75 @@ create_messages!(
         help: None,
     }
 
+    /// For when the language server fails to communicate with the editor.
+    @backtraced
+    language_server_error {
+        args: (error: impl Display),
+        msg: format!("The language server failed: {error}"),
+        help: None,
+    }
+
+    /// For when `conlang check` finds errors.
+    @backtraced
+    check_failed {
+        args: (count: usize),
+        msg: format!("Checking failed with {count} error(s)."),
+        help: None,
+    }
+
+    /// For when an input path is neither a file nor a directory, and not a valid glob pattern.
+    @backtraced
+    invalid_glob_pattern {
+        args: (pattern: impl Display, error: impl Display),
+        msg: format!("Invalid input pattern `{pattern}`: {error}"),
+        help: None,
+    }
+
+    /// For when no input files match a glob pattern or are found in a directory.
+    @backtraced
+    no_input_files {
+        args: (pattern: impl Display),
+        msg: format!("No input files were found for `{pattern}`."),
+        help: None,
+    }
+
+    /// For when a line of a JSON Lines file is not valid JSON.
+    @backtraced
+    invalid_json_line {
+        args: (name: impl Display, error: impl Display),
+        msg: format!("Failed to parse `{name}` as JSON: {error}"),
+        help: None,
+    }
+
+    /// For when some assignments of a batch fail.
+    @backtraced
+    batch_evaluation_failed {
+        args: (failed: usize, total: usize),
+        msg: format!("{failed} of {total} assignments did not satisfy the program."),
+        help: None,
+    }
+
+    /// For when the logger cannot be initialized.
+    @backtraced
+    failed_to_initialize_logger {
+        args: (error: impl Display),
+        msg: format!("Failed to initialize the logger: {error}"),
+        help: None,
+    }
+
+    /// For when a program with eliminated variables is exported as a conlang program, which cannot record them.
+    @backtraced
+    cannot_export_eliminated_variables {
+        args: (count: usize),
+        msg: format!("Cannot export a conlang program, since {count} variable(s) were eliminated from it."),
+        help: Some("Export with `--format json`, which records the eliminated variables.".to_string()),
+    }
+
+    /// For when a JSON Lines file is given to a streamed run.
+    @backtraced
+    cannot_stream_json_lines {
+        args: (path: impl Display),
+        msg: format!("Cannot evaluate the assignments of `{path}` while streaming the program."),
+        help: Some("Give each assignment in its own `.in` or `.json` file, or run without `--stream`.".to_string()),
+    }
 );
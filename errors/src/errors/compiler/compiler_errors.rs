@@ -14,8 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::create_messages;
-
+use conlang_span::Span;
 use std::{
     error::Error as ErrorArg,
     fmt::{Debug, Display},
@@ -36,10 +35,112 @@ create_messages!(
     }
 
     /// For when the evaluator cannot find a value for a variable during evaluation.
-    @backtraced
+    @formatted
     variable_not_assigned {
-        args: (variable: impl Display),
+        args: (variable: impl Display, suggestion: Option<String>),
         msg: format!("Cannot find assignment for `{variable}`."),
+        help: suggestion.map(|suggestion| format!("Did you mean `{suggestion}`?")),
+    }
+
+    /// For when a constraint is not satisfied by an input assignment.
+    @formatted
+    constraint_not_satisfied {
+        args: (a: impl Display, a_span: Span, b: impl Display, b_span: Span, c: impl Display, c_span: Span),
+        msg: format!("The constraint is not satisfied, since `{a} * {b} != {c}`."),
+        help: None,
+        labels: vec![
+            Label::new(a_span, format!("`A` evaluates to `{a}`")),
+            Label::new(b_span, format!("`B` evaluates to `{b}`")),
+            Label::new(c_span, format!("`C` evaluates to `{c}`")),
+        ],
+    }
+
+    /// For when the compiler can't write a file to the provided path.
+    @backtraced
+    file_write_error {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot write to the provided file path '{path:?}': {error}"),
+        help: None,
+    }
+
+    /// For when a constraint made only of constants does not hold, so no assignment can satisfy the program.
+    @formatted
+    constraint_never_satisfied {
+        args: (lhs: impl Display, rhs: impl Display),
+        msg: format!("The constraint is never satisfied, since `{lhs} != {rhs}`."),
+        help: Some("No assignment can satisfy the program. Check the constants of the constraint.".to_string()),
+    }
+
+    /// For when an assignment gives an eliminated variable a value other than the one it is recovered as.
+    @formatted
+    eliminated_variable_mismatch {
+        args: (variable: impl Display, value: impl Display, expected: impl Display),
+        msg: format!("`{variable}` is assigned `{value}`, but the program requires `{expected}`."),
+        help: Some(format!("`{variable}` was eliminated by an optimization. Its value is determined by the other variables.")),
+    }
+
+    /// For when a pipeline names a pass that is not registered.
+    @backtraced
+    unknown_pass {
+        args: (name: impl Display, available: impl Display),
+        msg: format!("Unknown pass `{name}`."),
+        help: Some(format!("The available passes are: {available}.")),
+    }
+
+    /// For when the dependencies of the passes in a pipeline form a cycle.
+    @backtraced
+    pass_dependency_cycle {
+        args: (passes: impl Display),
+        msg: format!("The dependencies of the passes {passes} form a cycle."),
+        help: None,
+    }
+
+    /// For when the AST of a stage is to be emitted, but the pass of the stage does not run.
+    @backtraced
+    stage_not_run {
+        args: (stage: impl Display, pass: impl Display),
+        msg: format!("Cannot emit the {stage} AST, since the `{pass}` pass does not run."),
+        help: Some(format!("Add `{pass}` to the passes, or do not skip it.")),
+    }
+
+    /// For when an import connects a port that is not a variable of the imported program.
+    @formatted
+    unknown_port {
+        args: (port: impl Display, package: impl Display),
+        msg: format!("The program of `{package}` has no variable `{port}`."),
+        help: None,
+    }
+
+    /// For when an import connects the same port more than once.
+    @formatted
+    duplicate_port {
+        args: (port: impl Display, first: Span),
+        msg: format!("The port `{port}` is connected more than once."),
+        help: None,
+        labels: vec![Label::new(first, "first connected here".to_string())],
+    }
+
+    /// For when a renamed variable of an imported program has the name of a variable of the importing program.
+    @formatted
+    import_name_clash {
+        args: (name: impl Display, package: impl Display),
+        msg: format!("The variable `{name}` of the import of `{package}` clashes with an existing variable."),
+        help: Some("Rename the existing variable, or import the program under another prefix.".to_string()),
+    }
+
+    /// For when a program has imports, but there are no dependencies to resolve them against.
+    @formatted
+    unresolved_import {
+        args: (package: impl Display),
+        msg: format!("Cannot resolve the import of `{package}`."),
+        help: Some("Imports are resolved against the dependencies of a project. Add the package with `conlang add`.".to_string()),
+    }
+
+    /// For when a program imports itself, directly or through other imports.
+    @formatted
+    import_cycle {
+        args: (packages: impl Display),
+        msg: format!("The imports {packages} form a cycle."),
         help: None,
     }
 
@@ -0,0 +1,42 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use conlang_span::Span;
+use std::fmt::Display;
+
+create_messages!(
+    /// CompilerWarning enum that represents all the warnings for the `conlang-compiler` crate.
+    CompilerWarning,
+    code_mask: 8000i32,
+    code_prefix: "CMP",
+
+    /// For when an input assignment contains a variable that the program does not use.
+    @formatted
+    unused_input_variable {
+        args: (variable: impl Display, suggestion: Option<String>),
+        msg: format!("The input variable `{variable}` is not used by the program."),
+        help: suggestion.map(|suggestion| format!("Did you mean `{suggestion}`?")),
+    }
+
+    /// For when a constraint is equivalent to an earlier one, and is removed.
+    @formatted
+    duplicate_constraint {
+        args: (first: Span),
+        msg: format!("This constraint is equivalent to an earlier one, and is removed."),
+        help: Some("Constraints are equivalent if they are equal up to swapping `A` and `B`, or scaling by constants.".to_string()),
+        labels: vec![Label::new(first, "the earlier constraint")],
+    }
+);
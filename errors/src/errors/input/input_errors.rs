@@ -14,8 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::create_messages;
-use std::fmt::Debug;
+use conlang_span::Span;
+use std::fmt::{Debug, Display};
 
 create_messages!(
     /// InputError enum that represents all the errors for the inputs part of  `conlang-ast` crate.
@@ -23,10 +23,36 @@ create_messages!(
     code_mask: 1000i32,
     code_prefix: "INP",
 
+    /// For when an input assignment assigns the same variable more than once.
     @formatted
     duplicate_input_variable {
-        args: (),
+        args: (variable: impl Display, first: Span),
         msg: format!("Variables in an input assignment must be unique."),
         help: None,
+        labels: vec![Label::new(first, format!("`{variable}` is first assigned here"))],
+    }
+
+    /// For when a JSON assignment is not an object.
+    @backtraced
+    json_assignment_not_an_object {
+        args: (value: impl Display),
+        msg: format!("A JSON assignment must be an object that maps variables to values, found `{value}`."),
+        help: None,
+    }
+
+    /// For when a value in a JSON assignment is not a field element.
+    @backtraced
+    invalid_json_input_value {
+        args: (variable: impl Display, value: impl Display),
+        msg: format!("The value `{value}` of `{variable}` is not a field element."),
+        help: Some("Values must be integers, or strings of integers, e.g., `{\"x\": \"2\"}`.".to_string()),
+    }
+
+    /// For when a JSON assignment is not valid JSON.
+    @backtraced
+    invalid_json_assignment {
+        args: (error: impl Display),
+        msg: format!("Failed to parse the assignment as JSON: {error}"),
+        help: None,
     }
 );
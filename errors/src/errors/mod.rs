@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::ConlangMessageCode;
+use crate::{ConlangMessageCode, Formatted};
 
 /// Contains the AST error definitions.
 pub mod ast;
@@ -32,6 +32,10 @@ pub use self::compiler::*;
 pub mod input;
 pub use self::input::*;
 
+/// Contains the Package error definitions.
+pub mod package;
+pub use self::package::*;
+
 /// Contains the Parser error definitions.
 pub mod parser;
 pub use self::parser::*;
@@ -56,6 +60,9 @@ pub enum ConlangError {
     /// Represents an Input Error in a Conlang Error.
     #[error(transparent)]
     InputError(#[from] InputError),
+    /// Represents an Package Error in a Conlang Error.
+    #[error(transparent)]
+    PackageError(#[from] PackageError),
     /// Represents an Parser Error in a Conlang Error.
     #[error(transparent)]
     ParserError(#[from] ParserError),
@@ -81,6 +88,7 @@ impl ConlangError {
             CompilerError(error) => error.error_code(),
             CliError(error) => error.error_code(),
             InputError(error) => error.error_code(),
+            PackageError(error) => error.error_code(),
             ParserError(error) => error.error_code(),
             TypeCheckerError(error) => error.error_code(),
             LastErrorCode(_) => unreachable!(),
@@ -97,12 +105,83 @@ impl ConlangError {
             CompilerError(error) => error.exit_code(),
             CliError(error) => error.exit_code(),
             InputError(error) => error.exit_code(),
+            PackageError(error) => error.exit_code(),
             ParserError(error) => error.exit_code(),
             TypeCheckerError(error) => error.exit_code(),
             LastErrorCode(code) => *code,
             Anyhow(_) => unimplemented!(), // todo: implement exit codes for snarkvm errors.
         }
     }
+
+    /// Returns the error if it points at a span in the source code.
+    pub fn formatted(&self) -> Option<&Formatted> {
+        use ConlangError::*;
+
+        match self {
+            AstError(error) => error.formatted(),
+            CompilerError(error) => error.formatted(),
+            CliError(error) => error.formatted(),
+            InputError(error) => error.formatted(),
+            PackageError(error) => error.formatted(),
+            ParserError(error) => error.formatted(),
+            TypeCheckerError(error) => error.formatted(),
+            LastErrorCode(_) | Anyhow(_) => None,
+        }
+    }
+
+    /// Returns the bare error message, without its code, location, or help.
+    pub fn message(&self) -> String {
+        use ConlangError::*;
+
+        match self {
+            AstError(error) => error.message().to_string(),
+            CompilerError(error) => error.message().to_string(),
+            CliError(error) => error.message().to_string(),
+            InputError(error) => error.message().to_string(),
+            PackageError(error) => error.message().to_string(),
+            ParserError(error) => error.message().to_string(),
+            TypeCheckerError(error) => error.message().to_string(),
+            LastErrorCode(_) | Anyhow(_) => self.to_string(),
+        }
+    }
+}
+
+/// The ConlangWarning type that contains all sub warning types.
+/// This allows a unified warning type throughout the Conlang crates.
+#[derive(Debug, Error)]
+pub enum ConlangWarning {
+    /// Represents an Compiler Warning in a Conlang Warning.
+    #[error(transparent)]
+    CompilerWarning(#[from] CompilerWarning),
+}
+
+impl ConlangWarning {
+    /// Implement warning code for each type of Warning.
+    pub fn warning_code(&self) -> String {
+        use ConlangWarning::*;
+
+        match self {
+            CompilerWarning(warning) => warning.warning_code(),
+        }
+    }
+
+    /// Returns the warning if it points at a span in the source code.
+    pub fn formatted(&self) -> Option<&Formatted> {
+        use ConlangWarning::*;
+
+        match self {
+            CompilerWarning(warning) => warning.formatted(),
+        }
+    }
+
+    /// Returns the bare warning message, without its code, location, or help.
+    pub fn message(&self) -> String {
+        use ConlangWarning::*;
+
+        match self {
+            CompilerWarning(warning) => warning.message().to_string(),
+        }
+    }
 }
 
 /// A global result type for all Conlang crates, that defaults the errors to be a ConlangError.
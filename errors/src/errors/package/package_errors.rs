@@ -0,0 +1,219 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    error::Error as ErrorArg,
+    fmt::{Debug, Display},
+};
+
+create_messages!(
+    /// PackageError enum that represents all the errors for the `conlang-package` crate.
+    PackageError,
+    code_mask: 5000i32,
+    code_prefix: "PAK",
+
+    /// For when a package name contains invalid characters.
+    @backtraced
+    invalid_package_name {
+        args: (name: impl Display),
+        msg: format!("Invalid project name `{name}`."),
+        help: Some("Project names must start with a letter and may only contain letters, numbers, `-` and `_`.".to_string()),
+    }
+
+    /// For when a new package would overwrite an existing directory.
+    @backtraced
+    package_already_exists {
+        args: (path: impl Debug),
+        msg: format!("Cannot create a project at {path:?}, since the path already exists."),
+        help: None,
+    }
+
+    /// For when there is no manifest in the project directory.
+    @backtraced
+    manifest_not_found {
+        args: (path: impl Debug),
+        msg: format!("Could not find a `conlang.toml` manifest in {path:?}."),
+        help: Some("Run `conlang new <NAME>` to create a project, or pass the file paths explicitly.".to_string()),
+    }
+
+    /// For when a directory of the package cannot be created.
+    @backtraced
+    failed_to_create_directory {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Failed to create the directory {path:?}: {error}"),
+        help: None,
+    }
+
+    /// For when a directory of the package cannot be removed.
+    @backtraced
+    failed_to_remove_directory {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Failed to remove the directory {path:?}: {error}"),
+        help: None,
+    }
+
+    /// For when a file of the package cannot be read.
+    @backtraced
+    failed_to_read_file {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Failed to read the file {path:?}: {error}"),
+        help: None,
+    }
+
+    /// For when a file of the package cannot be written.
+    @backtraced
+    failed_to_write_file {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Failed to write the file {path:?}: {error}"),
+        help: None,
+    }
+
+    /// For when the manifest is not valid TOML or is missing fields.
+    @backtraced
+    failed_to_parse_manifest {
+        args: (path: impl Debug, error: impl Display),
+        msg: format!("Failed to parse the manifest {path:?}: {error}"),
+        help: None,
+    }
+
+    /// For when the manifest cannot be serialized.
+    @backtraced
+    failed_to_serialize_manifest {
+        args: (error: impl Display),
+        msg: format!("Failed to serialize the manifest: {error}"),
+        help: None,
+    }
+
+    /// For when the version of a package is not a semantic version.
+    @backtraced
+    invalid_package_version {
+        args: (version: impl Display, error: impl Display),
+        msg: format!("Invalid version `{version}`: {error}"),
+        help: Some("Versions must follow semantic versioning, e.g., `0.1.0`.".to_string()),
+    }
+
+    /// For when a dependency version requirement cannot be parsed.
+    @backtraced
+    invalid_version_requirement {
+        args: (requirement: impl Display, error: impl Display),
+        msg: format!("Invalid version requirement `{requirement}`: {error}"),
+        help: None,
+    }
+
+    /// For when the registry has no package with the given name.
+    @backtraced
+    package_not_found_in_registry {
+        args: (name: impl Display, registry: impl Debug),
+        msg: format!("Could not find the package `{name}` in the registry at {registry:?}."),
+        help: None,
+    }
+
+    /// For when the registry has no version of a package that matches the requirement.
+    @backtraced
+    no_matching_version {
+        args: (name: impl Display, requirement: impl Display),
+        msg: format!("The registry has no version of `{name}` that matches `{requirement}`."),
+        help: None,
+    }
+
+    /// For when a version of a package is published twice.
+    @backtraced
+    version_already_published {
+        args: (name: impl Display, version: impl Display),
+        msg: format!("Version `{version}` of `{name}` is already published."),
+        help: Some("Bump the version in `conlang.toml` before publishing.".to_string()),
+    }
+
+    /// For when two packages require incompatible versions of the same dependency.
+    @backtraced
+    conflicting_dependency_versions {
+        args: (name: impl Display, version: impl Display, requirement: impl Display),
+        msg: format!("Version `{version}` of `{name}` is already used, but a package requires `{requirement}`."),
+        help: None,
+    }
+
+    /// For when a downloaded package does not match the checksum in the lockfile.
+    @backtraced
+    checksum_mismatch {
+        args: (name: impl Display, version: impl Display),
+        msg: format!("The checksum of `{name}` v{version} does not match the lockfile."),
+        help: Some("The package was changed after it was published. Delete `conlang.lock` to accept the new contents.".to_string()),
+    }
+
+    /// For when a package cannot be packed into an archive.
+    @backtraced
+    failed_to_create_archive {
+        args: (error: impl ErrorArg),
+        msg: format!("Failed to create the package archive: {error}"),
+        help: None,
+    }
+
+    /// For when a package archive cannot be unpacked.
+    @backtraced
+    failed_to_extract_archive {
+        args: (error: impl ErrorArg),
+        msg: format!("Failed to extract the package archive: {error}"),
+        help: None,
+    }
+
+    /// For when the lockfile is not valid TOML or is missing fields.
+    @backtraced
+    failed_to_parse_lockfile {
+        args: (path: impl Debug, error: impl Display),
+        msg: format!("Failed to parse the lockfile {path:?}: {error}"),
+        help: None,
+    }
+
+    /// For when the lockfile cannot be serialized.
+    @backtraced
+    failed_to_serialize_lockfile {
+        args: (error: impl Display),
+        msg: format!("Failed to serialize the lockfile: {error}"),
+        help: None,
+    }
+
+    /// For when an import refers to a package that is not a dependency.
+    @backtraced
+    unknown_dependency {
+        args: (name: impl Display),
+        msg: format!("`{name}` is not a dependency of the project."),
+        help: Some(format!("Run `conlang add {name}` to add it.")),
+    }
+
+    /// For when the registry location is not a directory.
+    @backtraced
+    unsupported_registry {
+        args: (location: impl Display),
+        msg: format!("Unsupported registry `{location}`."),
+        help: Some("The registry must be a directory path or a `file://` URL.".to_string()),
+    }
+
+    /// For when a dependency imports a package that is not one of its own dependencies.
+    @backtraced
+    unknown_dependency_of_package {
+        args: (name: impl Display, package: impl Display),
+        msg: format!("`{name}` is not a dependency of `{package}`."),
+        help: None,
+    }
+
+    /// For when the entry of a manifest is not a path inside of the project directory.
+    @backtraced
+    invalid_entry_path {
+        args: (package: impl Display, entry: impl Debug),
+        msg: format!("The entry {entry:?} of `{package}` is not inside of the project directory."),
+        help: Some("The entry must be a relative path without `..`, e.g. `src/main.conlang`.".to_string()),
+    }
+);
@@ -14,8 +14,6 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::create_messages;
-
 use std::fmt::{Debug, Display};
 
 create_messages!(
@@ -112,6 +110,14 @@ create_messages!(
         help: None,
     }
 
+    /// For when a streamed program has an import, which can only be composed into a whole program.
+    @formatted
+    import_in_stream {
+        args: (package: impl Display),
+        msg: format!("Cannot import `{package}` while streaming the constraints of a program."),
+        help: Some("Imports are composed into the whole program, so evaluate it without streaming.".to_string()),
+    }
+
 
 
 );
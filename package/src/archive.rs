@@ -0,0 +1,118 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Packing projects into zip archives for the registry, and unpacking them again.
+
+use crate::{Package, MANIFEST_FILENAME};
+
+use conlang_errors::{PackageError, Result};
+
+use std::{
+    fs,
+    io::{Cursor, Read, Write},
+    path::Path,
+};
+use walkdir::WalkDir;
+use zip::{write::FileOptions, ZipArchive, ZipWriter};
+
+/// Packs the manifest and the `src` directory of the `package` into a zip archive.
+pub fn pack(package: &Package) -> Result<Vec<u8>> {
+    let mut files = vec![package.root.join(MANIFEST_FILENAME)];
+    for entry in WalkDir::new(package.root.join("src")).sort_by_file_name() {
+        let entry = entry.map_err(PackageError::failed_to_create_archive)?;
+        if entry.file_type().is_file() {
+            files.push(entry.into_path());
+        }
+    }
+
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for file in files {
+        // Archive paths always use `/`, regardless of the platform.
+        let name = file.strip_prefix(&package.root).unwrap_or(&file);
+        let name = name.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+        let contents = fs::read(&file).map_err(|e| PackageError::failed_to_read_file(&file, e))?;
+
+        writer.start_file(name, FileOptions::default()).map_err(PackageError::failed_to_create_archive)?;
+        writer.write_all(&contents).map_err(PackageError::failed_to_create_archive)?;
+    }
+    let archive = writer.finish().map_err(PackageError::failed_to_create_archive)?;
+
+    Ok(archive.into_inner())
+}
+
+/// Unpacks the zip `archive` into the directory at `path`.
+/// Entries that would be written outside of `path` are rejected.
+pub fn unpack(archive: &[u8], path: &Path) -> Result<()> {
+    let mut archive = ZipArchive::new(Cursor::new(archive)).map_err(PackageError::failed_to_extract_archive)?;
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).map_err(PackageError::failed_to_extract_archive)?;
+        let Some(name) = file.enclosed_name().map(|name| path.join(name)) else {
+            let error = std::io::Error::new(std::io::ErrorKind::InvalidData, format!("invalid path `{}`", file.name()));
+            return Err(PackageError::failed_to_extract_archive(error).into());
+        };
+
+        if file.is_dir() {
+            fs::create_dir_all(&name).map_err(|e| PackageError::failed_to_create_directory(&name, e))?;
+            continue;
+        }
+        if let Some(parent) = name.parent() {
+            fs::create_dir_all(parent).map_err(|e| PackageError::failed_to_create_directory(parent, e))?;
+        }
+
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents).map_err(PackageError::failed_to_extract_archive)?;
+        fs::write(&name, contents).map_err(|e| PackageError::failed_to_write_file(&name, e))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a zip archive with a file for each of the `names`.
+    fn archive(names: &[&str]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for name in names {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(b"(x) * (y) = (z);\n").unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_unpack() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("package");
+
+        unpack(&archive(&["conlang.toml", "src/main.conlang"]), &path).unwrap();
+        assert!(path.join("conlang.toml").is_file());
+        assert!(path.join("src/main.conlang").is_file());
+    }
+
+    #[test]
+    fn test_unpack_rejects_paths_outside_of_the_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("package");
+
+        for name in ["../escaped.conlang", "src/../../escaped.conlang", "/escaped.conlang"] {
+            assert!(unpack(&archive(&[name]), &path).is_err(), "unpacked `{name}`");
+        }
+        assert!(!dir.path().join("escaped.conlang").exists());
+    }
+}
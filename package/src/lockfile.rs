@@ -0,0 +1,74 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use conlang_errors::{PackageError, Result};
+
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// The file name of the lockfile in the root directory of a project.
+pub const LOCKFILE_FILENAME: &str = "conlang.lock";
+
+/// The header written at the top of the lockfile.
+const LOCKFILE_HEADER: &str = "# This file is generated by conlang. It is not intended for manual editing.\n\n";
+
+/// The `conlang.lock` lockfile of a project, recording the exact version of each dependency.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// The resolved dependencies, including the dependencies of dependencies, sorted by name.
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockedPackage>,
+}
+
+/// A resolved dependency in the lockfile.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    /// The name of the package.
+    pub name: String,
+    /// The exact version of the package.
+    pub version: String,
+    /// The SHA-256 checksum of the package archive.
+    pub checksum: String,
+    /// The names of the packages it depends on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+}
+
+impl Lockfile {
+    /// Returns the locked package called `name`, if there is one.
+    pub fn get(&self, name: &str) -> Option<&LockedPackage> {
+        self.packages.iter().find(|package| package.name == name)
+    }
+
+    /// Reads the lockfile from the directory at `path`, or returns an empty lockfile if there is none.
+    pub fn read_from_dir(path: &Path) -> Result<Self> {
+        let path = path.join(LOCKFILE_FILENAME);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+
+        let string = fs::read_to_string(&path).map_err(|e| PackageError::failed_to_read_file(&path, e))?;
+        toml::from_str(&string).map_err(|e| PackageError::failed_to_parse_lockfile(&path, e).into())
+    }
+
+    /// Writes the lockfile to the directory at `path`.
+    pub fn write_to_dir(&self, path: &Path) -> Result<()> {
+        let path = path.join(LOCKFILE_FILENAME);
+        let string = toml::to_string(self).map_err(PackageError::failed_to_serialize_lockfile)?;
+        fs::write(&path, format!("{LOCKFILE_HEADER}{string}"))
+            .map_err(|e| PackageError::failed_to_write_file(&path, e).into())
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use conlang_errors::{PackageError, Result};
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The file name of the manifest in the root directory of a project.
+pub const MANIFEST_FILENAME: &str = "conlang.toml";
+
+/// The `conlang.toml` manifest of a project.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub package: PackageMetadata,
+    /// The packages the project depends on, mapped to their version requirements.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, String>,
+}
+
+/// The `[package]` section of the manifest.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageMetadata {
+    /// The name of the project.
+    pub name: String,
+    /// The version of the project.
+    pub version: String,
+    /// The field that the constraints are defined over.
+    pub field: FieldType,
+    /// The path to the program, relative to the project root.
+    pub entry: PathBuf,
+    /// The paths to the input files, relative to the project root.
+    #[serde(default)]
+    pub inputs: Vec<PathBuf>,
+}
+
+/// The fields that constraints can be defined over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldType {
+    /// The scalar field of the BLS12-377 curve.
+    #[serde(rename = "bls12_377")]
+    Bls12377,
+}
+
+impl Manifest {
+    /// Returns the manifest of a new project called `name`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            package: PackageMetadata {
+                name: name.to_string(),
+                version: "0.1.0".to_string(),
+                field: FieldType::Bls12377,
+                entry: PathBuf::from("src/main.conlang"),
+                inputs: vec![PathBuf::from("inputs/main.in")],
+            },
+            dependencies: BTreeMap::new(),
+        }
+    }
+
+    /// Returns whether there is a manifest in the directory at `path`.
+    pub fn exists_at(path: &Path) -> bool {
+        path.join(MANIFEST_FILENAME).is_file()
+    }
+
+    /// Reads the manifest from the directory at `path`.
+    pub fn read_from_dir(path: &Path) -> Result<Self> {
+        let path = path.join(MANIFEST_FILENAME);
+        if !path.is_file() {
+            return Err(PackageError::manifest_not_found(path.parent().unwrap_or(&path)).into());
+        }
+
+        let string = fs::read_to_string(&path).map_err(|e| PackageError::failed_to_read_file(&path, e))?;
+        toml::from_str(&string).map_err(|e| PackageError::failed_to_parse_manifest(&path, e).into())
+    }
+
+    /// Writes the manifest to the directory at `path`.
+    pub fn write_to_dir(&self, path: &Path) -> Result<()> {
+        let path = path.join(MANIFEST_FILENAME);
+        let string = toml::to_string(self).map_err(PackageError::failed_to_serialize_manifest)?;
+        fs::write(&path, string).map_err(|e| PackageError::failed_to_write_file(&path, e).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manifest = Manifest::new("square");
+        manifest.dependencies.insert("utils".to_string(), "^1.2".to_string());
+
+        manifest.write_to_dir(dir.path()).unwrap();
+        assert!(Manifest::exists_at(dir.path()));
+        assert_eq!(Manifest::read_from_dir(dir.path()).unwrap(), manifest);
+
+        // A manifest without dependencies or inputs can be written by hand.
+        let string = "[package]\nname = \"square\"\nversion = \"0.1.0\"\nfield = \"bls12_377\"\n\
+                      entry = \"main.conlang\"\n";
+        fs::write(dir.path().join(MANIFEST_FILENAME), string).unwrap();
+        let manifest = Manifest::read_from_dir(dir.path()).unwrap();
+        assert_eq!(manifest.package.entry, PathBuf::from("main.conlang"));
+        assert!(manifest.package.inputs.is_empty() && manifest.dependencies.is_empty());
+
+        let err = Manifest::read_from_dir(&dir.path().join("missing")).unwrap_err();
+        assert!(err.message().contains("manifest"), "{}", err.message());
+    }
+}
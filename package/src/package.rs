@@ -0,0 +1,290 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{archive, checksum, LockedPackage, Lockfile, Manifest, Registry};
+
+use conlang_errors::{PackageError, Result};
+
+use semver::{Version, VersionReq};
+use std::{
+    collections::VecDeque,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// The name of the directory that dependencies are installed into.
+pub const IMPORTS_DIRECTORY_NAME: &str = "imports";
+
+/// The program of a new project.
+const MAIN_PROGRAM: &str = "\
+// The entry point of the project.
+// Each constraint has the form `(A) * (B) = (C);`, where `A`, `B` and `C` are linear combinations.
+(x) * (y) = (z);
+";
+
+/// The input file of a new project.
+const MAIN_INPUT: &str = "\
+x = 2;
+y = 3;
+z = 6;
+";
+
+/// The `.gitignore` of a new project, which ignores the installed dependencies.
+const GITIGNORE: &str = "imports/\n";
+
+/// A conlang project, i.e., a directory with a `conlang.toml` manifest.
+#[derive(Clone, Debug)]
+pub struct Package {
+    /// The root directory of the project.
+    pub root: PathBuf,
+    /// The manifest of the project.
+    pub manifest: Manifest,
+}
+
+impl Package {
+    /// Opens the project in the directory at `path`.
+    /// The entry must be inside of the project directory, since the manifest of a dependency is not trusted.
+    pub fn open(path: &Path) -> Result<Self> {
+        let manifest = Manifest::read_from_dir(path)?;
+        let entry = &manifest.package.entry;
+        if !entry.components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir)) {
+            return Err(PackageError::invalid_entry_path(&manifest.package.name, entry).into());
+        }
+        Ok(Self { root: path.to_path_buf(), manifest })
+    }
+
+    /// Creates a new project called `name` in the directory at `path`, which must not exist yet.
+    pub fn initialize(name: &str, path: &Path) -> Result<Self> {
+        if !Self::is_package_name_valid(name) {
+            return Err(PackageError::invalid_package_name(name).into());
+        }
+        if path.exists() {
+            return Err(PackageError::package_already_exists(path).into());
+        }
+
+        let package = Self { root: path.to_path_buf(), manifest: Manifest::new(name) };
+
+        for dir in ["src", "inputs"] {
+            let dir = path.join(dir);
+            fs::create_dir_all(&dir).map_err(|e| PackageError::failed_to_create_directory(&dir, e))?;
+        }
+        package.manifest.write_to_dir(path)?;
+        let files = [
+            (package.entry_path(), MAIN_PROGRAM),
+            (path.join("inputs/main.in"), MAIN_INPUT),
+            (path.join(".gitignore"), GITIGNORE),
+        ];
+        for (file, contents) in files {
+            fs::write(&file, contents).map_err(|e| PackageError::failed_to_write_file(&file, e))?;
+        }
+
+        Ok(package)
+    }
+
+    /// Returns whether `name` is a valid project name.
+    /// A name starts with a letter and contains only letters, numbers, `-` and `_`.
+    pub fn is_package_name_valid(name: &str) -> bool {
+        let mut chars = name.chars();
+        matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    /// Returns the path to the program of the project.
+    pub fn entry_path(&self) -> PathBuf {
+        self.root.join(&self.manifest.package.entry)
+    }
+
+    /// Returns the paths to the input files of the project.
+    pub fn input_paths(&self) -> Vec<PathBuf> {
+        self.manifest.package.inputs.iter().map(|input| self.root.join(input)).collect()
+    }
+
+    /// Returns the version of the project.
+    pub fn version(&self) -> Result<Version> {
+        let version = &self.manifest.package.version;
+        Version::parse(version).map_err(|e| PackageError::invalid_package_version(version, e).into())
+    }
+
+    /// Returns the path to the program of the dependency called `name`, which must be installed.
+    /// It is imported by the dependency called `importer`, or by the project itself if there is none.
+    /// Since all dependencies are installed into the `imports` directory of the project,
+    /// only the manifest of the importer decides whether it may import `name`.
+    pub fn resolve_import(&self, importer: Option<&str>, name: &str) -> Result<PathBuf> {
+        match importer {
+            Some(importer) => {
+                if !Self::open(&self.dependency_path(importer)?)?.manifest.dependencies.contains_key(name) {
+                    return Err(PackageError::unknown_dependency_of_package(name, importer).into());
+                }
+            }
+            None => {
+                if !self.manifest.dependencies.contains_key(name) {
+                    return Err(PackageError::unknown_dependency(name).into());
+                }
+            }
+        }
+        Ok(Self::open(&self.dependency_path(name)?)?.entry_path())
+    }
+
+    /// Returns the directory that the dependency called `name` is installed into.
+    /// The name must be a valid package name, so that the directory cannot be outside of the `imports` directory.
+    fn dependency_path(&self, name: &str) -> Result<PathBuf> {
+        match Self::is_package_name_valid(name) {
+            true => Ok(self.root.join(IMPORTS_DIRECTORY_NAME).join(name)),
+            false => Err(PackageError::invalid_package_name(name).into()),
+        }
+    }
+
+    /// Resolves the dependencies of the project and of its dependencies against the `registry`,
+    /// installs them into the `imports` directory, and records them in the lockfile.
+    /// Versions in an existing lockfile are kept as long as they satisfy the manifest.
+    pub fn install_dependencies(&self, registry: &Registry) -> Result<Lockfile> {
+        let previous = Lockfile::read_from_dir(&self.root)?;
+
+        let mut lockfile = Lockfile::default();
+        let mut queue: VecDeque<_> = self.manifest.dependencies.clone().into_iter().collect();
+        while let Some((name, requirement)) = queue.pop_front() {
+            // Check the name before it is used in a path, since it comes from the manifest of a dependency.
+            let path = self.dependency_path(&name)?;
+            let requirement = VersionReq::parse(&requirement)
+                .map_err(|e| PackageError::invalid_version_requirement(&requirement, e))?;
+
+            // Each package is installed once, so every requirement on it must accept the same version.
+            if let Some(locked) = lockfile.get(&name) {
+                if !Version::parse(&locked.version).map_or(false, |version| requirement.matches(&version)) {
+                    return Err(
+                        PackageError::conflicting_dependency_versions(&name, &locked.version, requirement).into()
+                    );
+                }
+                continue;
+            }
+
+            // Prefer the locked version, so that installs are reproducible.
+            let locked = previous.get(&name);
+            let version = match locked.and_then(|locked| Version::parse(&locked.version).ok()) {
+                Some(version) if requirement.matches(&version) => version,
+                _ => registry.resolve(&name, &requirement)?,
+            };
+
+            let archive = registry.fetch(&name, &version)?;
+            let checksum = checksum(&archive);
+            if let Some(locked) = locked.filter(|locked| locked.version == version.to_string()) {
+                if locked.checksum != checksum {
+                    return Err(PackageError::checksum_mismatch(&name, &version).into());
+                }
+            }
+
+            // Replace any previously installed version of the package.
+            if path.exists() {
+                fs::remove_dir_all(&path).map_err(|e| PackageError::failed_to_remove_directory(&path, e))?;
+            }
+            archive::unpack(&archive, &path)?;
+
+            let dependency = Self::open(&path)?;
+            queue.extend(dependency.manifest.dependencies.clone());
+            lockfile.packages.push(LockedPackage {
+                name,
+                version: version.to_string(),
+                checksum,
+                dependencies: dependency.manifest.dependencies.into_keys().collect(),
+            });
+        }
+
+        lockfile.packages.sort_by(|a, b| a.name.cmp(&b.name));
+        lockfile.write_to_dir(&self.root)?;
+
+        Ok(lockfile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_names() {
+        for name in ["square", "my-package", "my_package2"] {
+            assert!(Package::is_package_name_valid(name), "`{name}` is valid");
+        }
+        for name in ["", "2d", "-package", "../package", "a/b", "a\\b", ".", "a b"] {
+            assert!(!Package::is_package_name_valid(name), "`{name}` is invalid");
+        }
+    }
+
+    #[test]
+    fn test_dependency_names_are_checked() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("project");
+        let mut package = Package::initialize("project", &root).unwrap();
+        let registry = Registry::new(dir.path().join("registry"));
+
+        // A dependency whose name is a path must not be looked up or installed outside of the project.
+        package.manifest.dependencies.insert("../../escaped".to_string(), "*".to_string());
+        assert!(package.install_dependencies(&registry).is_err());
+        assert!(package.resolve_import(None, "../../escaped").is_err());
+        assert!(package.resolve_import(Some("../../escaped"), "project").is_err());
+        assert!(registry.versions("../escaped").is_err());
+        assert!(registry.fetch("../escaped", &Version::new(0, 1, 0)).is_err());
+        assert!(!dir.path().join("escaped").exists());
+    }
+
+    /// Publishes a new project called `name` to the `registry`, which depends on the `dependencies`.
+    fn publish(dir: &Path, registry: &Registry, name: &str, dependencies: &[&str]) {
+        let mut package = Package::initialize(name, &dir.join(name)).unwrap();
+        for dependency in dependencies {
+            package.manifest.dependencies.insert(dependency.to_string(), "0.1.0".to_string());
+        }
+        package.manifest.write_to_dir(&package.root).unwrap();
+        registry.publish(&package).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_nested_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = Registry::new(dir.path().join("registry"));
+        publish(dir.path(), &registry, "c", &[]);
+        publish(dir.path(), &registry, "b", &["c"]);
+        let mut package = Package::initialize("a", &dir.path().join("a")).unwrap();
+        package.manifest.dependencies.insert("b".to_string(), "0.1.0".to_string());
+        package.install_dependencies(&registry).unwrap();
+
+        // Each import is resolved against the dependencies of the package that imports it.
+        let imports = package.root.join(IMPORTS_DIRECTORY_NAME);
+        assert_eq!(package.resolve_import(None, "b").unwrap(), imports.join("b/src/main.conlang"));
+        assert_eq!(package.resolve_import(Some("b"), "c").unwrap(), imports.join("c/src/main.conlang"));
+        let err = package.resolve_import(None, "c").unwrap_err();
+        assert_eq!(err.message(), "`c` is not a dependency of the project.");
+        let err = package.resolve_import(Some("c"), "b").unwrap_err();
+        assert_eq!(err.message(), "`b` is not a dependency of `c`.");
+    }
+
+    #[test]
+    fn test_entry_must_be_inside_of_the_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut package = Package::initialize("project", &dir.path().join("project")).unwrap();
+
+        // An entry that is absolute or goes up a directory could point to any file.
+        for entry in ["/etc/passwd", "../secret.conlang", "src/../../secret.conlang"] {
+            package.manifest.package.entry = PathBuf::from(entry);
+            package.manifest.write_to_dir(&package.root).unwrap();
+            let err = Package::open(&package.root).unwrap_err();
+            assert!(err.message().contains("is not inside of the project directory"), "{}", err.message());
+        }
+
+        package.manifest.package.entry = PathBuf::from("./main.conlang");
+        package.manifest.write_to_dir(&package.root).unwrap();
+        assert!(Package::open(&package.root).is_ok());
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{archive, Package};
+
+use conlang_errors::{PackageError, Result};
+
+use semver::{Version, VersionReq};
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The file extension of package archives.
+const ARCHIVE_EXTENSION: &str = "zip";
+
+/// A registry of published packages in a directory.
+/// Each version of a package is stored as a zip archive at `<name>/<version>.zip`.
+#[derive(Clone, Debug)]
+pub struct Registry {
+    /// The root directory of the registry.
+    root: PathBuf,
+}
+
+impl Registry {
+    /// Returns the registry in the directory at `root`.
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Returns the registry at `location`, which is a directory path or a `file://` URL.
+    pub fn from_location(location: &str) -> Result<Self> {
+        match location.split_once("://") {
+            None => Ok(Self::new(PathBuf::from(location))),
+            Some(("file", path)) => Ok(Self::new(PathBuf::from(path))),
+            Some(_) => Err(PackageError::unsupported_registry(location).into()),
+        }
+    }
+
+    /// Returns the root directory of the registry.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the published versions of the package called `name`, in ascending order.
+    pub fn versions(&self, name: &str) -> Result<Vec<Version>> {
+        let dir = self.package_dir(name)?;
+        let entries = fs::read_dir(&dir).map_err(|_| PackageError::package_not_found_in_registry(name, &self.root))?;
+
+        let mut versions: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().map_or(false, |extension| extension == ARCHIVE_EXTENSION))
+            .filter_map(|path| Version::parse(path.file_stem()?.to_str()?).ok())
+            .collect();
+        versions.sort();
+
+        Ok(versions)
+    }
+
+    /// Returns the latest version of the package called `name` that matches the `requirement`.
+    pub fn resolve(&self, name: &str, requirement: &VersionReq) -> Result<Version> {
+        self.versions(name)?
+            .into_iter()
+            .rev()
+            .find(|version| requirement.matches(version))
+            .ok_or_else(|| PackageError::no_matching_version(name, requirement).into())
+    }
+
+    /// Returns the archive of the given version of the package called `name`.
+    pub fn fetch(&self, name: &str, version: &Version) -> Result<Vec<u8>> {
+        let path = self.archive_path(name, version)?;
+        fs::read(&path).map_err(|e| PackageError::failed_to_read_file(&path, e).into())
+    }
+
+    /// Publishes the `package` to the registry and returns the path to its archive.
+    /// Published versions are immutable, so publishing a version twice is an error.
+    pub fn publish(&self, package: &Package) -> Result<PathBuf> {
+        let name = &package.manifest.package.name;
+        let version = package.version()?;
+
+        let path = self.archive_path(name, &version)?;
+        if path.exists() {
+            return Err(PackageError::version_already_published(name, version).into());
+        }
+
+        let archive = archive::pack(package)?;
+        let dir = self.package_dir(name)?;
+        fs::create_dir_all(&dir).map_err(|e| PackageError::failed_to_create_directory(&dir, e))?;
+        fs::write(&path, archive).map_err(|e| PackageError::failed_to_write_file(&path, e))?;
+
+        Ok(path)
+    }
+
+    /// Returns the directory of the versions of the package called `name`.
+    /// The name must be a valid package name, so that the directory cannot be outside of the registry.
+    fn package_dir(&self, name: &str) -> Result<PathBuf> {
+        match Package::is_package_name_valid(name) {
+            true => Ok(self.root.join(name)),
+            false => Err(PackageError::invalid_package_name(name).into()),
+        }
+    }
+
+    /// Returns the path to the archive of the given version of the package called `name`.
+    fn archive_path(&self, name: &str, version: &Version) -> Result<PathBuf> {
+        Ok(self.package_dir(name)?.join(format!("{version}.{ARCHIVE_EXTENSION}")))
+    }
+}
+
+/// Returns the hex-encoded SHA-256 checksum of an `archive`.
+pub fn checksum(archive: &[u8]) -> String {
+    hex::encode(Sha256::digest(archive))
+}
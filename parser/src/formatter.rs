@@ -0,0 +1,111 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The formatter to convert Conlang code text into its canonical layout.
+//!
+//! Formatting works on tokens rather than the AST, so that comments are preserved.
+
+use crate::{tokenize, SpannedToken, Token};
+
+use conlang_errors::Result;
+use conlang_span::span::{BytePos, Pos};
+
+/// Returns the given program or input `source` in canonical layout.
+///
+/// Each statement is placed on its own line, tokens are separated by single spaces,
+/// and at most one blank line is kept between statements.
+pub fn format_source(source: &str) -> Result<String> {
+    let tokens = tokenize(source, BytePos(0))?;
+
+    let mut output = String::new();
+    let mut prev: Option<&SpannedToken> = None;
+    // Whether the previous token is a `-` negating the current token, e.g., in `(-x)`.
+    let mut negation = false;
+    for token in tokens.iter() {
+        if let Some(prev) = prev {
+            // The number of line breaks between the previous token and this one in the source.
+            // A line comment contains its own line break.
+            let newlines = source[prev.span.hi.to_usize()..token.span.lo.to_usize()].matches('\n').count()
+                + matches!(prev.token, Token::CommentLine(_)) as usize;
+
+            let line_break = match (&prev.token, &token.token) {
+                (Token::CommentLine(_), _) => true,
+                // A comment on the same line as the end of a statement trails it.
+                (Token::Semicolon, token) => !is_comment(token) || newlines > 0,
+                (Token::CommentBlock(_), _) => newlines > 0,
+                (_, token) => is_comment(token) && newlines > 0,
+            };
+
+            if line_break {
+                output.push('\n');
+                // Keep a single blank line where the source has one or more.
+                if newlines > 1 {
+                    output.push('\n');
+                }
+            } else if !negation && needs_space(&prev.token, &token.token) {
+                output.push(' ');
+            }
+
+            negation = token.token == Token::Sub
+                && (line_break
+                    || matches!(prev.token, Token::LeftParen | Token::Add | Token::Sub | Token::Mul | Token::Assign));
+        } else {
+            negation = token.token == Token::Sub;
+        }
+
+        match &token.token {
+            Token::CommentLine(comment) => output.push_str(comment.trim_end()),
+            token => output.push_str(&token.to_string()),
+        }
+
+        prev = Some(token);
+    }
+
+    if !output.is_empty() {
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// Returns whether `token` is a comment.
+fn is_comment(token: &Token) -> bool {
+    matches!(token, Token::CommentLine(_) | Token::CommentBlock(_))
+}
+
+/// Returns whether a space separates `token` from the `prev`ious token on the same line.
+fn needs_space(prev: &Token, token: &Token) -> bool {
+    !matches!((prev, token), (_, Token::RightParen | Token::Comma | Token::Semicolon) | (Token::LeftParen, _))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_source() {
+        let source = "// A comment.\n(x)*( -1 -  -3*y )=(z) ; /* trailing */\n\n\n\n(0 + x)\n  * (y) = (z);// end\n";
+        assert_eq!(
+            format_source(source).unwrap(),
+            "// A comment.\n(x) * (-1 - -3 * y) = (z); /* trailing */\n\n(0 + x) * (y) = (z); // end\n"
+        );
+        assert_eq!(format_source("x=2;y =  3;").unwrap(), "x = 2;\ny = 3;\n");
+        assert_eq!(
+            format_source("import sha256 as sha( input=x ,digest = y );(sha.w)*(1)=(y);").unwrap(),
+            "import sha256 as sha (input = x, digest = y);\n(sha.w) * (1) = (y);\n"
+        );
+    }
+}
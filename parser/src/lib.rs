@@ -24,6 +24,10 @@
 
 pub(crate) mod tokenizer;
 pub(crate) use tokenizer::*;
+pub use tokenizer::lexer::is_identifier;
+
+pub mod formatter;
+pub use formatter::*;
 
 pub mod parser;
 pub use parser::*;
@@ -20,7 +20,7 @@ use crate::{tokenizer::*, Token};
 
 use conlang_ast::*;
 use conlang_errors::{emitter::Handler, ParserError, Result};
-use conlang_span::Span;
+use conlang_span::{Span, Symbol};
 
 use std::{fmt::Display, mem};
 
@@ -146,7 +146,7 @@ impl<'a> ParserContext<'a> {
 
     /// At the previous token, return and make a variable with `name`.
     fn mk_ident_prev(&self, name: String) -> Variable {
-        Variable { name, span: self.prev_token.span }
+        Variable { name: Symbol::intern(&name), span: self.prev_token.span }
     }
 
     /// Eats the next token if it is a variable and returns it.
@@ -25,6 +25,7 @@ use conlang_ast::*;
 use conlang_errors::{emitter::Handler, Result};
 
 use conlang_span::span::BytePos;
+use std::iter;
 
 mod context;
 use context::ParserContext;
@@ -43,6 +44,67 @@ pub fn parse_program(handler: &Handler, source: &str, start_pos: BytePos) -> Res
     context.parse_program()
 }
 
+/// Parses the constraints of a program from the given `source` code text, one statement at a time.
+///
+/// Unlike [`parse_program()`], only the tokens of the current statement are held in memory,
+/// so the constraints of a large program can be evaluated while the rest of it is parsed.
+/// The program cannot have imports. The iterator ends after the first error.
+/// ```rust
+/// use conlang_errors::emitter::Handler;
+/// use conlang_span::span::BytePos;
+///
+/// # conlang_span::session_globals::create_session_if_not_set_then(|_| {
+/// let handler = Handler::default();
+/// let source = "(x) * (y) = (z);\n(z) * (z) = (w);\n// The end.\n";
+/// let mut constraints = conlang_parser::parse_constraints(&handler, source, BytePos(0));
+///
+/// assert_eq!(constraints.next().unwrap().unwrap().to_string(), "(x) * (y) = (z);");
+/// assert_eq!(constraints.next().unwrap().unwrap().to_string(), "(z) * (z) = (w);");
+/// assert!(constraints.next().is_none());
+/// # });
+/// ```
+pub fn parse_constraints<'a>(
+    handler: &'a Handler,
+    source: &'a str,
+    start_pos: BytePos,
+) -> impl Iterator<Item = Result<Constraint>> + 'a {
+    // Tokenize the input lazily.
+    let mut tokens = crate::tokenize_iter(source, start_pos);
+    let mut failed = false;
+    iter::from_fn(move || {
+        if failed {
+            return None;
+        }
+
+        // Collect the tokens of the next statement, which ends with a semicolon.
+        let mut statement = Vec::new();
+        for token in tokens.by_ref() {
+            match token {
+                Ok(token) => {
+                    let end = token.token == Token::Semicolon;
+                    statement.push(token);
+                    if end {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    failed = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        // Only comments may follow the last statement.
+        if statement.iter().all(|token| matches!(token.token, Token::CommentLine(_) | Token::CommentBlock(_))) {
+            return None;
+        }
+
+        // Parse the statement in its own parser context.
+        let constraint = ParserContext::new(handler, statement).parse_streamed_constraint();
+        failed = constraint.is_err();
+        Some(constraint)
+    })
+}
+
 /// Parses an input file at the given file `path` and `source` code text.
 pub fn parse_input(handler: &Handler, source: &str, start_pos: BytePos) -> Result<Assignment> {
     // Tokenize the input.
@@ -52,3 +114,31 @@ pub fn parse_input(handler: &Handler, source: &str, start_pos: BytePos) -> Resul
     // Parse the input file.
     context.parse_input()
 }
+
+/// Parses a single constraint from the given `source` code text.
+pub fn parse_constraint(handler: &Handler, source: &str, start_pos: BytePos) -> Result<Constraint> {
+    // Tokenize the input.
+    let tokens = crate::tokenize(source, start_pos)?;
+    // Create a new parser context.
+    let mut context = ParserContext::new(handler, tokens);
+    // Parse the constraint, which must span the entire source.
+    let constraint = context.parse_constraint()?;
+    match context.has_next() {
+        true => context.unexpected("end of input"),
+        false => Ok(constraint),
+    }
+}
+
+/// Parses a single linear combination from the given `source` code text.
+pub fn parse_linear_combination(handler: &Handler, source: &str, start_pos: BytePos) -> Result<LinearCombination> {
+    // Tokenize the input.
+    let tokens = crate::tokenize(source, start_pos)?;
+    // Create a new parser context.
+    let mut context = ParserContext::new(handler, tokens);
+    // Parse the linear combination, which must span the entire source.
+    let linear_combination = context.parse_linear_combination()?;
+    match context.has_next() {
+        true => context.unexpected("end of input"),
+        false => Ok(linear_combination),
+    }
+}
@@ -15,12 +15,226 @@
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
+use crate::Delimiter;
 
-use conlang_errors::Result;
+use conlang_errors::{ParserError, Result};
+use conlang_span::Span;
 
 impl ParserContext<'_> {
     /// Returns an [`Program`] struct filled with the data acquired in the program file.
     pub(crate) fn parse_program(&mut self) -> Result<Program> {
-        todo!()
+        let start = self.token.span;
+        let mut imports = Vec::new();
+        let mut constraints = Vec::new();
+
+        while self.has_next() {
+            if self.check_keyword("import") {
+                imports.push(self.parse_import()?);
+            } else {
+                constraints.push(self.parse_constraint()?);
+            }
+        }
+
+        // The program spans its statements, and an empty program has no source location.
+        let span = match imports.is_empty() && constraints.is_empty() {
+            true => Span::dummy(),
+            false => start + self.prev_token.span,
+        };
+
+        Ok(Program { imports, constraints, span, ..Default::default() })
+    }
+
+    /// Returns the [`Constraint`] of a statement of a streamed program.
+    /// Errors on an import, since it can only be composed into the whole program.
+    pub(super) fn parse_streamed_constraint(&mut self) -> Result<Constraint> {
+        if self.check_keyword("import") {
+            let import = self.parse_import()?;
+            return Err(ParserError::import_in_stream(import.package, import.span).into());
+        }
+        self.parse_constraint()
+    }
+
+    /// Returns an [`Import`] of the form `import package as prefix (inner = outer, ...);`, where the ports are optional.
+    fn parse_import(&mut self) -> Result<Import> {
+        let start = self.expect_keyword("import")?;
+        let package = self.parse_variable()?.name.to_string();
+        self.expect_keyword("as")?;
+        let prefix = self.parse_variable()?.name.to_string();
+
+        let mut ports = Vec::new();
+        if self.peek_is_left_par() {
+            (ports, ..) = self.parse_list(Delimiter::Parenthesis, Some(Token::Comma), |p| {
+                let inner = p.parse_variable()?;
+                p.expect(&Token::Assign)?;
+                let outer = p.parse_variable()?;
+                Ok(Some(Port { inner, outer }))
+            })?;
+        }
+        let end = self.expect(&Token::Semicolon)?;
+
+        Ok(Import { package, prefix, ports, span: start + end })
+    }
+
+    /// Returns whether the current token is the identifier `keyword`.
+    /// Keywords are contextual, so they remain valid variable names.
+    fn check_keyword(&self, keyword: &str) -> bool {
+        matches!(&self.token.token, Token::Identifier(name) if name == keyword)
+    }
+
+    /// Eats the identifier `keyword`, or errors.
+    fn expect_keyword(&mut self, keyword: &str) -> Result<Span> {
+        match self.check_keyword(keyword) {
+            true => {
+                self.bump();
+                Ok(self.prev_token.span)
+            }
+            false => self.unexpected(format!("'{keyword}'")),
+        }
+    }
+
+    /// Returns a [`Constraint`] of the form `(A) * (B) = (C);`.
+    pub(super) fn parse_constraint(&mut self) -> Result<Constraint> {
+        let start = self.token.span;
+        // Parse the left-hand side of the product.
+        let a = self.parse_parenthesized_linear_combination()?;
+        self.expect(&Token::Mul)?;
+        // Parse the right-hand side of the product.
+        let b = self.parse_parenthesized_linear_combination()?;
+        self.expect(&Token::Assign)?;
+        // Parse the result of the product.
+        let c = self.parse_parenthesized_linear_combination()?;
+        let end = self.expect(&Token::Semicolon)?;
+
+        Ok(Constraint { span: start + end, ..Constraint::new(a, b, c) })
+    }
+
+    /// Returns a [`LinearCombination`] delimited by parentheses.
+    fn parse_parenthesized_linear_combination(&mut self) -> Result<LinearCombination> {
+        self.expect(&Token::LeftParen)?;
+        let linear_combination = self.parse_linear_combination()?;
+        self.expect(&Token::RightParen)?;
+        Ok(linear_combination)
+    }
+
+    /// Returns a [`LinearCombination`].
+    /// A linear combination begins with an optional constant, followed by a sequence of terms.
+    pub(super) fn parse_linear_combination(&mut self) -> Result<LinearCombination> {
+        let start = self.token.span;
+        let mut linear_combination = LinearCombination::default();
+
+        // Parse the leading constant or term, which may be negated.
+        let negate = self.eat(&Token::Sub);
+        if self.check_int() && !self.look_ahead(1, |next| next.token == Token::Mul) {
+            let mut constant = self.parse_constant()?;
+            if negate {
+                constant.negate();
+            }
+            linear_combination.constant = constant;
+        } else {
+            let mut term = self.parse_term()?;
+            if negate {
+                term.negate();
+            }
+            linear_combination.terms.push(term);
+        }
+
+        // Parse the remaining terms, each of which is added or subtracted.
+        while !self.check(&Token::RightParen) && self.has_next() {
+            self.expect_any(&[Token::Add, Token::Sub])?;
+            let negate = self.prev_token.token == Token::Sub;
+            let mut term = self.parse_term()?;
+            if negate {
+                term.negate();
+            }
+            linear_combination.terms.push(term);
+        }
+
+        linear_combination.span = start + self.prev_token.span;
+        Ok(linear_combination)
+    }
+
+    /// Returns a [`Term`] of the form `c * x` or `x`, where `c` is a constant and `x` is a variable.
+    fn parse_term(&mut self) -> Result<Term> {
+        if self.check_int() {
+            let constant = self.parse_constant()?;
+            self.expect(&Token::Mul)?;
+            let variable = self.parse_variable()?;
+            Ok(Term::new(constant, variable))
+        } else if let Some(variable) = self.eat_variable() {
+            // A variable without a coefficient is implicitly multiplied by one.
+            let constant = Constant { span: variable.span, ..Constant::one() };
+            Ok(Term::new(constant, variable))
+        } else {
+            self.unexpected("integer literal or identifier")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_constraint, parse_linear_combination};
+    use conlang_errors::emitter::Handler;
+    use conlang_span::{session_globals::create_session_if_not_set_then, span::BytePos};
+
+    #[test]
+    fn test_coefficients() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let linear_combination = parse_linear_combination(&handler, "2 * x - y + 3 * z", BytePos(0)).unwrap();
+
+            let terms: Vec<_> = linear_combination
+                .terms
+                .iter()
+                .map(|term| (term.constant.to_string(), term.variable.name.to_string()))
+                .collect();
+            assert_eq!(terms, [("2".into(), "x".into()), ("-1".into(), "y".into()), ("3".into(), "z".into())]);
+            assert!(linear_combination.constant.is_zero());
+        });
+    }
+
+    #[test]
+    fn test_constants() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+
+            // A leading integer is a constant, unless it is the coefficient of a term.
+            let linear_combination = parse_linear_combination(&handler, "-5 + x", BytePos(0)).unwrap();
+            assert_eq!(linear_combination.constant.to_string(), "-5");
+            assert_eq!(linear_combination.terms.len(), 1);
+
+            let linear_combination = parse_linear_combination(&handler, "-5 * x", BytePos(0)).unwrap();
+            assert!(linear_combination.constant.is_zero());
+            assert_eq!(linear_combination.terms[0].constant.to_string(), "-5");
+
+            let constraint = parse_constraint(&handler, "(1) * (7) = (7)", BytePos(0));
+            assert!(constraint.is_err(), "a constraint ends with a semicolon");
+            let constraint = parse_constraint(&handler, "(1) * (7) = (7);", BytePos(0)).unwrap();
+            assert!(constraint.a.terms.is_empty() && constraint.a.constant.is_one());
+            assert_eq!(constraint.to_string(), "(1) * (7) = (7);");
+
+            // Only the leading constant is allowed, later ones must be coefficients.
+            assert!(parse_linear_combination(&handler, "x + 5", BytePos(0)).is_err());
+            assert!(parse_linear_combination(&handler, "1 + 5", BytePos(0)).is_err());
+        });
+    }
+
+    #[test]
+    fn test_malformed_constraints() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            for source in [
+                "(x) (y) = (z);",
+                "(x) * (y) (z);",
+                "x * (y) = (z);",
+                "(x) * (y) = ();",
+                "(x) * (y) = (z",
+                "(2 *) * (y) = (z);",
+                "(x y) * (y) = (z);",
+                "(x + ) * (y) = (z);",
+                "(x) * (y) = (z); (x) * (y) = (z);",
+            ] {
+                assert!(parse_constraint(&handler, source, BytePos(0)).is_err(), "parsed `{source}`");
+            }
+        });
     }
 }
@@ -25,9 +25,22 @@ use std::{
 };
 
 /// Eat an identifier, that is, a string matching '[a-zA-Z][a-zA-Z\d_]*', if any.
-fn eat_identifier(input: &mut Peekable<impl Iterator<Item = char>>) -> Option<String> {
+/// Identifiers may be qualified by the prefixes of imports, e.g., `sha.w`, which are separated by `.`.
+fn eat_identifier(input: &mut Peekable<impl Iterator<Item = char> + Clone>) -> Option<String> {
     input.peek().filter(|c| c.is_ascii_alphabetic())?;
-    Some(from_fn(|| input.next_if(|c| c.is_ascii_alphanumeric() || c == &'_')).collect())
+    let mut identifier: String = from_fn(|| input.next_if(|c| c.is_ascii_alphanumeric() || c == &'_')).collect();
+    // A `.` continues the identifier only if another segment follows it.
+    while input.peek() == Some(&'.') && input.clone().nth(1).map_or(false, |c| c.is_ascii_alphabetic()) {
+        identifier.push(input.next().unwrap());
+        identifier.extend(from_fn(|| input.next_if(|c| c.is_ascii_alphanumeric() || c == &'_')));
+    }
+    Some(identifier)
+}
+
+/// Returns whether `name` is a single identifier, e.g., a variable name such as `x` or `sha.w`.
+pub fn is_identifier(name: &str) -> bool {
+    let mut input = name.chars().peekable();
+    eat_identifier(&mut input).is_some() && input.next().is_none()
 }
 
 /// Checks if a char is a Unicode Bidirectional Override code point
@@ -87,6 +100,7 @@ impl Token {
             x if x.is_ascii_digit() => return Self::eat_integer(&mut input),
             '(' => return match_one(&mut input, Token::LeftParen),
             ')' => return match_one(&mut input, Token::RightParen),
+            ',' => return match_one(&mut input, Token::Comma),
             '*' => return match_one(&mut input, Token::Mul),
             '+' => return match_one(&mut input, Token::Add),
             '-' => return match_one(&mut input, Token::Sub),
@@ -70,91 +70,17 @@ mod tests {
     fn test_tokenizer() {
         create_session_if_not_set_then(|s| {
             let raw = r#"
-    "test"
-    "test{}test"
-    "test{}"
-    "{}test"
-    "test{"
-    "test}"
-    "test{test"
-    "test}test"
-    "te{{}}"
     test_ident
+    x1
     12345
-    address
-    assert
-    assert_eq
-    assert_neq
-    async
-    bool
-    const
-    else
-    false
-    field
-    finalize
-    for
-    function
-    group
-    i128
-    i64
-    i32
-    i16
-    i8
-    if
-    in
-    inline
-    input
-    let
-    mut
-    private
-    program
-    public
-    return
-    scalar
-    self
-    string
-    struct
-    test
-    then
-    transition
-    true
-    u128
-    u64
-    u32
-    u16
-    u8
-    console
-    !
-    !=
-    &&
+    0
     (
     )
     *
-    **
     +
-    ,
     -
-    ->
-    =>
-    _
-    .
-    ..
-    /
-    :
-    ;
-    <
-    <=
     =
-    ==
-    >
-    >=
-    [
-    ]
-    {{
-    }}
-    ||
-    ?
-    @
+    ;
     // test
     /* test */
     //"#;
@@ -167,7 +93,7 @@ mod tests {
 
             assert_eq!(
                 output,
-                r#""test" "test{}test" "test{}" "{}test" "test{" "test}" "test{test" "test}test" "te{{}}" test_ident 12345 address assert assert_eq assert_neq async bool const else false field finalize for function group i128 i64 i32 i16 i8 if in inline input let mut private program public return scalar self string struct test then transition true u128 u64 u32 u16 u8 console ! != && ( ) * ** + , - -> => _ . .. / : ; < <= = == > >= [ ] { { } } || ? @ // test
+                r#"test_ident x1 12345 0 ( ) * + - = ; // test
  /* test */ // "#
             );
         });
@@ -201,4 +127,14 @@ ppp            test
             }
         })
     }
+
+    #[test]
+    fn test_is_identifier() {
+        for name in ["x", "sig_A", "x1", "first.output", "a.b.c_2"] {
+            assert!(is_identifier(name), "`{name}` is an identifier");
+        }
+        for name in ["", "1x", "_x", "x.", ".x", "x.1", "x y", "x-y", "(x)"] {
+            assert!(!is_identifier(name), "`{name}` is not an identifier");
+        }
+    }
 }
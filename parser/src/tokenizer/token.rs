@@ -35,6 +35,7 @@ pub enum Token {
     Assign,
     LeftParen,
     RightParen,
+    Comma,
     Semicolon,
 
     // Meta Tokens
@@ -58,6 +59,7 @@ impl fmt::Display for Token {
             Assign => write!(f, "="),
             LeftParen => write!(f, "("),
             RightParen => write!(f, ")"),
+            Comma => write!(f, ","),
             Semicolon => write!(f, ";"),
 
             Eof => write!(f, "<eof>"),
@@ -0,0 +1,106 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use indexmap::{IndexMap, IndexSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    hash::Hash,
+};
+
+/// A type whose values can be combined into one, such as the outputs of visiting the children of a node.
+///
+/// Combining must be associative, and `Default::default()` must be its identity,
+/// so that the outputs of any number of children can be combined in order.
+pub trait Combine: Default {
+    /// Returns the combination of `self` with `other`, where `other` comes after `self`.
+    fn combine(self, other: Self) -> Self;
+
+    /// Returns the combination of all `values`, in order.
+    fn combine_all(values: impl IntoIterator<Item = Self>) -> Self {
+        values.into_iter().fold(Self::default(), Self::combine)
+    }
+}
+
+impl Combine for () {
+    fn combine(self, _other: Self) -> Self {}
+}
+
+impl Combine for usize {
+    /// Counts are combined by adding them.
+    fn combine(self, other: Self) -> Self {
+        self + other
+    }
+}
+
+impl<T> Combine for Vec<T> {
+    fn combine(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl<T: Combine> Combine for Option<T> {
+    /// `None` is the identity, and two values are combined with each other.
+    fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Some(lhs), Some(rhs)) => Some(lhs.combine(rhs)),
+            (lhs, None) => lhs,
+            (None, rhs) => rhs,
+        }
+    }
+}
+
+impl<T: Hash + Eq> Combine for IndexSet<T> {
+    fn combine(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl<T: Ord> Combine for BTreeSet<T> {
+    fn combine(mut self, mut other: Self) -> Self {
+        self.append(&mut other);
+        self
+    }
+}
+
+impl<K: Hash + Eq, V: Combine> Combine for IndexMap<K, V> {
+    /// The values of keys in both maps are combined.
+    fn combine(mut self, other: Self) -> Self {
+        for (key, value) in other {
+            let entry = self.entry(key).or_default();
+            *entry = std::mem::take(entry).combine(value);
+        }
+        self
+    }
+}
+
+impl<K: Ord, V: Combine> Combine for BTreeMap<K, V> {
+    /// The values of keys in both maps are combined.
+    fn combine(mut self, other: Self) -> Self {
+        for (key, value) in other {
+            let entry = self.entry(key).or_default();
+            *entry = std::mem::take(entry).combine(value);
+        }
+        self
+    }
+}
+
+impl<A: Combine, B: Combine> Combine for (A, B) {
+    fn combine(self, other: Self) -> Self {
+        (self.0.combine(other.0), self.1.combine(other.1))
+    }
+}
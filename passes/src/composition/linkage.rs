@@ -0,0 +1,50 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use conlang_ast::Assignment;
+use conlang_span::Symbol;
+
+use indexmap::IndexMap;
+
+/// A `Linkage` records how the variables of an imported program are named in the program it was composed into.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Linkage {
+    /// The name of the imported package.
+    pub package: String,
+    /// The prefix of the renamed variables.
+    pub prefix: String,
+    /// The name of each variable of the imported program in the composed program.
+    pub variables: IndexMap<Symbol, Symbol>,
+}
+
+impl Linkage {
+    /// Returns the name of the variable `name` of the imported program in the composed program.
+    /// A name that the imported program does not use is prefixed.
+    pub fn resolve(&self, name: Symbol) -> Symbol {
+        match self.variables.get(&name) {
+            Some(resolved) => *resolved,
+            None => Symbol::intern(&format!("{}.{name}", self.prefix)),
+        }
+    }
+
+    /// Returns the `assignment` of the imported program, with its variables renamed as in the composed program.
+    pub fn translate(&self, assignment: &Assignment) -> Assignment {
+        Assignment {
+            map: assignment.map.iter().map(|(name, value)| (self.resolve(*name), value.clone())).collect(),
+            spans: assignment.spans.iter().map(|(name, span)| (self.resolve(*name), *span)).collect(),
+        }
+    }
+}
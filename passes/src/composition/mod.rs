@@ -0,0 +1,159 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+mod linkage;
+pub use linkage::*;
+
+use crate::{Reconstructor, Replacer};
+
+use conlang_ast::{Import, Program, Variable};
+use conlang_errors::{CompilerError, Result};
+use conlang_span::Symbol;
+
+use indexmap::IndexMap;
+
+/// Composes programs by instantiating one inside another.
+///
+/// The variables of the instantiated program are renamed with a [`Replacer`], so that they cannot capture
+/// the variables of the program it is composed into: the ports of the [`Import`] are renamed to the variables
+/// they are connected to, and every other variable `w` is renamed to `prefix.w`.
+/// ```rust
+/// use conlang_ast::{Import, ProgramBuilder};
+/// use conlang_passes::Compose;
+/// use conlang_span::Symbol;
+///
+/// # conlang_span::session_globals::create_session_if_not_set_then(|_| {
+/// // A subcircuit that squares its input.
+/// let mut builder = ProgramBuilder::new();
+/// let (input, output) = (builder.var("input"), builder.var("output"));
+/// builder.enforce(input.clone(), input, output);
+/// let square = builder.build();
+///
+/// // A program that computes `x^4` with two instances of the subcircuit.
+/// let mut program = ProgramBuilder::new().build();
+/// program.compose(square.clone(), &Import::new("square", "first").with_port("input", "x")).unwrap();
+/// let linkage = program
+///     .compose(square, &Import::new("square", "second").with_port("input", "first.output").with_port("output", "y"))
+///     .unwrap();
+///
+/// assert_eq!(program.to_string(), "(x) * (x) = (first.output);\n(first.output) * (first.output) = (y);\n");
+/// assert_eq!(linkage.resolve(Symbol::intern("output")), Symbol::intern("y"));
+/// # });
+/// ```
+pub trait Compose {
+    /// Instantiates the `subprogram` as described by the `import`, and returns how its variables were renamed.
+    /// Errors if a port is not a variable of the `subprogram`, or a renamed variable is already in use.
+    /// The imports of the `subprogram` are discarded, so they have to be composed into it beforehand.
+    fn compose(&mut self, subprogram: Program, import: &Import) -> Result<Linkage>;
+}
+
+impl Compose for Program {
+    fn compose(&mut self, subprogram: Program, import: &Import) -> Result<Linkage> {
+        let names = subprogram.variable_names();
+
+        // The ports are connected to the variables of this program.
+        let mut ports = IndexMap::new();
+        for port in import.ports.iter() {
+            if !names.contains(&port.inner.name) {
+                return Err(CompilerError::unknown_port(&port.inner, &import.package, port.inner.span).into());
+            }
+            if let Some(first) = ports.insert(port.inner.name, port) {
+                return Err(CompilerError::duplicate_port(&port.inner, first.inner.span, port.inner.span).into());
+            }
+        }
+
+        // Every other variable is renamed under the prefix, which must not capture a variable of this program.
+        let mut existing = self.variable_names();
+        existing.extend(ports.values().map(|port| port.outer.name));
+        let mut variables = IndexMap::with_capacity(names.len());
+        for name in names {
+            let renamed = match ports.get(&name) {
+                Some(port) => port.outer.name,
+                None => Symbol::intern(&format!("{}.{name}", import.prefix)),
+            };
+            if !ports.contains_key(&name) && existing.contains(&renamed) {
+                return Err(CompilerError::import_name_clash(renamed, &import.package, import.span).into());
+            }
+            variables.insert(name, renamed);
+        }
+
+        let subprogram = Program { imports: Vec::new(), ..subprogram };
+        let mut replacer =
+            Replacer::new(|variable: &Variable| Variable { name: variables[&variable.name], ..variable.clone() });
+        let subprogram = replacer.reconstruct(subprogram);
+        self.constraints.extend(subprogram.constraints);
+        self.eliminated.extend(subprogram.eliminated);
+
+        Ok(Linkage { package: import.package.clone(), prefix: import.prefix.clone(), variables })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conlang_ast::Assignment;
+    use conlang_errors::emitter::Handler;
+    use conlang_span::{session_globals::create_session_if_not_set_then, span::BytePos};
+
+    /// Returns the program of `source`.
+    fn parse(source: &str) -> Program {
+        conlang_parser::parse_program(&Handler::default(), source, BytePos(0)).unwrap()
+    }
+
+    #[test]
+    fn test_compose() {
+        create_session_if_not_set_then(|_| {
+            let square = parse("(input) * (input) = (output);\n(output) * (1) = (w);\n");
+            let mut program = parse("import square as sq (input = x, output = y);\n(y) * (y) = (z);\n");
+            let import = program.imports.remove(0);
+
+            // The ports are connected, and the other variables are prefixed.
+            let linkage = program.compose(square, &import).unwrap();
+            assert_eq!(program.to_string(), "(y) * (y) = (z);\n(x) * (x) = (y);\n(y) * (1) = (sq.w);\n");
+            let variables: Vec<_> = linkage.variables.iter().map(|(inner, outer)| format!("{inner} = {outer}")).collect();
+            assert_eq!(variables, ["input = x", "output = y", "w = sq.w"]);
+
+            // An assignment of the imported program is translated into one of the composed program.
+            let assignment = Assignment::from_json_string(r#"{"input": "2", "output": "4", "w": "4"}"#).unwrap();
+            let translated = linkage.translate(&assignment);
+            let names: Vec<_> = translated.map.keys().map(|name| name.as_str()).collect();
+            assert_eq!(names, ["x", "y", "sq.w"]);
+        });
+    }
+
+    #[test]
+    fn test_compose_errors() {
+        create_session_if_not_set_then(|_| {
+            let square = parse("(input) * (input) = (output);\n");
+            let compose = |source: &str| {
+                let mut program = parse(source);
+                let import = program.imports.remove(0);
+                program.compose(square.clone(), &import).unwrap_err().message()
+            };
+
+            assert_eq!(compose("import square as sq (in = x);\n"), "The program of `square` has no variable `in`.");
+            assert_eq!(
+                compose("import square as sq (input = x, input = y);\n"),
+                "The port `input` is connected more than once."
+            );
+            // A prefixed variable must not capture a variable of the importing program.
+            assert_eq!(
+                compose("import square as sq (input = x);\n(sq.output) * (1) = (x);\n"),
+                "The variable `sq.output` of the import of `square` clashes with an existing variable."
+            );
+        });
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Reconstructor;
+
+use conlang_ast::*;
+use conlang_errors::{emitter::Handler, CompilerError};
+
+/// A `ConstantFolder` evaluates the parts of a program that only depend on constants.
+///
+/// A constraint made only of constants is removed if it holds, and reported as an error otherwise.
+/// A constraint where `A` or `B` is the constant `0` or `1` is linear, and is rewritten into the form `(L) * (1) = (R)`:
+/// - `(1) * (B) = (C)` becomes `(B) * (1) = (C)`.
+/// - `(0) * (B) = (C)` becomes `(C) * (1) = (0)`.
+///
+/// A linear constraint whose sides are both constants is then folded as well.
+pub struct ConstantFolder<'a> {
+    /// The handler used to report constraints that are never satisfied.
+    handler: &'a Handler,
+}
+
+impl<'a> ConstantFolder<'a> {
+    /// The name of the pass, which it records in the provenance of the constraints it changes.
+    pub const NAME: &'static str = "fold";
+
+    /// Returns a new constant folder, which reports errors through the `handler`.
+    pub fn new(handler: &'a Handler) -> Self {
+        Self { handler }
+    }
+
+    /// Returns the folded `constraint`, or `None` if it always holds and can be removed.
+    fn fold_constraint(&mut self, constraint: Constraint) -> Option<Constraint> {
+        let Constraint { a, b, c, span, mut provenance } = constraint;
+
+        let (a, b, c) = match (as_constant(&a), as_constant(&b)) {
+            // A product of constants is compared with `C` directly.
+            (Some(a_value), Some(b_value)) => ((a_value.clone() * b_value.clone()).into(), one(), c),
+            (Some(factor), _) | (_, Some(factor)) if factor.is_zero() => (c, one(), zero()),
+            (Some(factor), _) if factor.is_one() => (b, one(), c),
+            // Otherwise, `A` or `B` has terms, so the constraint cannot be folded.
+            _ => return Some(Constraint { a, b, c, span, provenance }),
+        };
+        provenance.record_pass(Self::NAME);
+        let folded = Constraint { a, b, c, span, provenance };
+
+        // A linear constraint is decided if both of its sides are constants.
+        match (as_constant(&folded.a), as_constant(&folded.b), as_constant(&folded.c)) {
+            (Some(lhs), Some(factor), Some(rhs)) if factor.is_one() => {
+                if lhs.value != rhs.value {
+                    self.handler.emit_err(CompilerError::constraint_never_satisfied(lhs, rhs, span));
+                }
+                None
+            }
+            _ => Some(folded),
+        }
+    }
+}
+
+impl Reconstructor for ConstantFolder<'_> {
+    type AdditionalOutput = ();
+
+    fn reconstruct_constraint(&mut self, constraint: Constraint) -> (Option<Constraint>, Self::AdditionalOutput) {
+        (self.fold_constraint(constraint), Default::default())
+    }
+}
+
+/// Returns the value of the `linear_combination` if it has no terms.
+fn as_constant(linear_combination: &LinearCombination) -> Option<&Constant> {
+    match linear_combination.terms.is_empty() {
+        true => Some(&linear_combination.constant),
+        false => None,
+    }
+}
+
+/// Returns the linear combination `1`, without a source location.
+fn one() -> LinearCombination {
+    Constant::one().into()
+}
+
+/// Returns the linear combination `0`, without a source location.
+fn zero() -> LinearCombination {
+    Constant::default().into()
+}
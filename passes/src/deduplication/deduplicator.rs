@@ -0,0 +1,139 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Normalizer, Reconstructor};
+
+use conlang_ast::*;
+use conlang_errors::{emitter::Handler, CompilerWarning};
+use conlang_span::Span;
+
+use std::collections::{hash_map::Entry, HashMap};
+
+/// A `Deduplicator` removes constraints that are equivalent to an earlier constraint of the program.
+///
+/// Constraints are compared in a canonical form, in which
+/// - the terms of each linear combination are combined and sorted by variable,
+/// - `A` and `B` are scaled so that their leading coefficient is one, and `C` is scaled accordingly, and
+/// - `A` and `B` are ordered, since their product is commutative.
+///
+/// Each removed constraint is reported as a warning that points at both occurrences.
+pub struct Deduplicator<'a> {
+    /// The handler used to report removed constraints.
+    handler: &'a Handler,
+    /// Maps the canonical form of each constraint kept so far to its span.
+    seen: HashMap<String, Span>,
+}
+
+impl<'a> Deduplicator<'a> {
+    /// The name of the pass. It only removes constraints, so it never records itself in their provenance.
+    pub const NAME: &'static str = "dedup";
+
+    /// Returns a new deduplicator, which reports removed constraints through the `handler`.
+    pub fn new(handler: &'a Handler) -> Self {
+        Self { handler, seen: HashMap::new() }
+    }
+}
+
+impl Reconstructor for Deduplicator<'_> {
+    type AdditionalOutput = ();
+
+    fn reconstruct_constraint(&mut self, constraint: Constraint) -> (Option<Constraint>, Self::AdditionalOutput) {
+        match self.seen.entry(canonical_form(&constraint)) {
+            Entry::Occupied(first) => {
+                self.handler.emit_warning(CompilerWarning::duplicate_constraint(*first.get(), constraint.span));
+                (None, Default::default())
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(constraint.span);
+                (Some(constraint), Default::default())
+            }
+        }
+    }
+}
+
+/// Returns the canonical form of the `constraint`, which is equal for equivalent constraints.
+fn canonical_form(constraint: &Constraint) -> String {
+    let (a, a_factor) = scaled(&constraint.a);
+    let (b, b_factor) = scaled(&constraint.b);
+    // `(k * A) * (l * B) = C` is equivalent to `(A) * (B) = (C / (k * l))`.
+    let c = sorted(&constraint.c) * (a_factor * b_factor);
+
+    let (a, b, c) = (a.to_string(), b.to_string(), c.to_string());
+    match a <= b {
+        true => format!("({a}) * ({b}) = ({c})"),
+        false => format!("({b}) * ({a}) = ({c})"),
+    }
+}
+
+/// Returns the `linear_combination` with its terms combined and sorted by variable.
+fn sorted(linear_combination: &LinearCombination) -> LinearCombination {
+    let mut linear_combination = Normalizer.reconstruct_linear_combination(linear_combination.clone()).0;
+    linear_combination.terms.sort_by_key(|term| term.variable.name.as_str());
+    linear_combination
+}
+
+/// Returns the sorted `linear_combination` scaled so that its leading coefficient is one, and the scaling factor.
+/// The leading coefficient is that of the first term, or the constant if there are no terms.
+fn scaled(linear_combination: &LinearCombination) -> (LinearCombination, Constant) {
+    let linear_combination = sorted(linear_combination);
+    let leading = match linear_combination.terms.first() {
+        Some(term) => &term.constant,
+        None => &linear_combination.constant,
+    };
+    // A zero linear combination is left as it is.
+    let factor = leading.inverse().unwrap_or_else(Constant::one);
+    (linear_combination * factor.clone(), factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Deduplicator, Pass};
+    use conlang_errors::emitter::Handler;
+    use conlang_span::{session_globals::create_session_if_not_set_then, span::BytePos};
+
+    /// Returns the program of `source` without its duplicate constraints, and the number of warnings.
+    fn deduplicate(source: &str) -> (String, usize) {
+        let (handler, buffer) = Handler::new_with_buf();
+        let program = conlang_parser::parse_program(&handler, source, BytePos(0)).unwrap();
+        let program = Deduplicator::do_pass((program, &handler));
+        (program.to_string(), buffer.extract_warnings().into_inner().len())
+    }
+
+    #[test]
+    fn test_equivalent_constraints() {
+        create_session_if_not_set_then(|_| {
+            // Constraints are equivalent up to the order of terms, swapping `A` and `B`, and scaling.
+            let source = "(x + y) * (z) = (w);\n(y + x) * (z) = (w);\n(z) * (x + y) = (w);\n\
+                          (2 * x + 2 * y) * (3 * z) = (6 * w);\n(-x - y) * (-z) = (w);\n(x - x + y + x) * (z) = (w);\n";
+            assert_eq!(deduplicate(source), ("(x + y) * (z) = (w);\n".to_string(), 5));
+
+            // The first occurrence is kept.
+            let source = "(2) * (x) = (y);\n(1) * (x) = (y);\n(x) * (4) = (2 * y);\n";
+            assert_eq!(deduplicate(source), ("(2) * (x) = (y);\n(1) * (x) = (y);\n".to_string(), 1));
+        });
+    }
+
+    #[test]
+    fn test_distinct_constraints() {
+        create_session_if_not_set_then(|_| {
+            let source = "(x + y) * (z) = (w);\n(x + y) * (z) = (2 * w);\n(x - y) * (z) = (w);\n\
+                          (x + y) * (z) = (1 + w);\n(0) * (x) = (y);\n(0) * (z) = (y);\n";
+            let (program, warnings) = deduplicate(source);
+            assert_eq!(program, source);
+            assert_eq!(warnings, 0);
+        });
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+mod deduplicator;
+pub use deduplicator::*;
+
+use crate::{Pass, Reconstructor};
+
+use conlang_ast::Program;
+use conlang_errors::emitter::Handler;
+
+impl<'a> Pass for Deduplicator<'a> {
+    type Input = (Program, &'a Handler);
+    type Output = Program;
+
+    fn do_pass((program, handler): Self::Input) -> Self::Output {
+        Deduplicator::new(handler).reconstruct(program)
+    }
+}
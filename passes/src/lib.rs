@@ -17,14 +17,41 @@
 #![forbid(unsafe_code)]
 #![doc = include_str!("../README.md")]
 
+pub mod combine;
+pub use self::combine::*;
+
+pub mod composition;
+pub use self::composition::*;
+
+pub mod constant_folding;
+pub use self::constant_folding::*;
+
+pub mod deduplication;
+pub use self::deduplication::*;
+
+pub mod linear_elimination;
+pub use self::linear_elimination::*;
+
+pub mod normalization;
+pub use self::normalization::*;
+
 pub mod pass;
 pub use self::pass::*;
 
+pub mod pass_manager;
+pub use self::pass_manager::*;
+
 pub mod reconstructor;
 pub use self::reconstructor::*;
 
 pub mod replacer;
 pub use self::replacer::*;
 
+pub mod span_eraser;
+pub use self::span_eraser::*;
+
+pub mod statistics;
+pub use self::statistics::*;
+
 pub mod visitor;
 pub use visitor::*;
@@ -0,0 +1,279 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Normalizer, Reconstructor};
+
+use conlang_ast::*;
+use conlang_span::{Span, Symbol};
+
+use indexmap::IndexMap;
+
+/// A `LinearEliminator` uses linear constraints to eliminate variables from a program.
+///
+/// A constraint is linear if `A` or `B` is a constant `k`, so that it states `k * L = C`.
+/// Such a constraint is solved for one of its variables, and removed from the program.
+/// The variable is substituted by its value in every other constraint,
+/// and recorded in [`Program::eliminated`], so that its value can still be computed from an assignment.
+/// A constraint whose variables cancel out after substitution is rewritten into `(k) * (1) = (0)`,
+/// so that constant folding removes it or reports it.
+///
+/// Public variables are never eliminated, since they have to remain part of the circuit.
+pub struct LinearEliminator<'a> {
+    /// The variables that must not be eliminated.
+    public_variables: &'a [String],
+    /// The number of constraints each variable occurs in, used to pick the variable to eliminate.
+    occurrences: IndexMap<Symbol, usize>,
+    /// Maps each eliminated variable to its value, which only uses variables that remain in the program.
+    substitutions: IndexMap<Symbol, EliminatedVariable>,
+    /// Maps each eliminated variable to the spans of the source constraints that its value was derived from.
+    sources: IndexMap<Symbol, Vec<Span>>,
+}
+
+impl<'a> LinearEliminator<'a> {
+    /// The name of the pass, which it records in the provenance of the constraints it changes.
+    pub const NAME: &'static str = "eliminate";
+
+    /// Returns a new linear eliminator, which keeps the `public_variables` in the program.
+    pub fn new(public_variables: &'a [String]) -> Self {
+        Self {
+            public_variables,
+            occurrences: IndexMap::new(),
+            substitutions: IndexMap::new(),
+            sources: IndexMap::new(),
+        }
+    }
+
+    /// Returns the variable that the linear constraint `difference = 0` is solved for, and its value.
+    /// The `span` is that of the constraint.
+    fn solve(&self, mut difference: LinearCombination, span: Span) -> Option<EliminatedVariable> {
+        // The variable that occurs in the fewest constraints is eliminated, so that substituting it adds few terms.
+        let (index, _) = difference
+            .terms
+            .iter()
+            .enumerate()
+            .filter(|(_, term)| !self.public_variables.iter().any(|public| term.variable.name.as_str() == public.as_str()))
+            .min_by_key(|(_, term)| self.occurrences.get(&term.variable.name).copied().unwrap_or_default())?;
+        let term = difference.terms.remove(index);
+
+        // `c * x + R = 0` is solved as `x = R * (-1 / c)`.
+        let inverse = (-term.constant).inverse()?;
+        Some(EliminatedVariable { variable: term.variable, value: difference * inverse, span })
+    }
+}
+
+impl Reconstructor for LinearEliminator<'_> {
+    type AdditionalOutput = ();
+
+    fn reconstruct_program(&mut self, program: Program) -> (Program, Self::AdditionalOutput) {
+        for constraint in program.constraints.iter() {
+            for name in [&constraint.a, &constraint.b, &constraint.c]
+                .into_iter()
+                .flat_map(|linear_combination| linear_combination.terms.iter())
+                .map(|term| term.variable.name)
+            {
+                *self.occurrences.entry(name).or_default() += 1;
+            }
+        }
+
+        let mut constraints = Vec::with_capacity(program.constraints.len());
+        for constraint in program.constraints {
+            let Some(constraint) = self.reconstruct_constraint(constraint).0 else {
+                continue;
+            };
+            let Some(difference) = linear_difference(&constraint) else {
+                constraints.push(constraint);
+                continue;
+            };
+            // A constraint whose variables cancel out is decided by its constant, which constant folding checks.
+            // A constraint made only of constants is left as it is, so that folding reports it as it was written.
+            let has_variables = [&constraint.a, &constraint.b, &constraint.c].iter().any(|side| !side.terms.is_empty());
+            if difference.terms.is_empty() && has_variables {
+                let (a, b, c) = (difference.constant.into(), Constant::one().into(), Constant::default().into());
+                let mut constraint = Constraint { a, b, c, ..constraint };
+                constraint.provenance.record_pass(Self::NAME);
+                constraints.push(constraint);
+                continue;
+            }
+            match self.solve(difference, constraint.span) {
+                Some(eliminated) => {
+                    // The value is derived from the constraint, and from the sources recorded for it so far.
+                    let mut sources = vec![constraint.span];
+                    sources.extend(constraint.provenance.sources.iter().copied());
+                    // The values of earlier eliminations may use the variable, which is replaced as well.
+                    for (name, substitution) in self.substitutions.iter_mut() {
+                        if substitution.value.terms.iter().any(|term| term.variable.name == eliminated.variable.name) {
+                            self.sources.entry(*name).or_default().extend(sources.iter().copied());
+                        }
+                        let value = std::mem::take(&mut substitution.value);
+                        substitution.value =
+                            substitute(value, |name| (name == eliminated.variable.name).then_some(&eliminated.value));
+                    }
+                    self.sources.insert(eliminated.variable.name, sources);
+                    self.substitutions.insert(eliminated.variable.name, eliminated);
+                }
+                None => constraints.push(constraint),
+            }
+        }
+
+        // The constraints kept before a variable was eliminated may still use it.
+        let constraints =
+            constraints.into_iter().filter_map(|constraint| self.reconstruct_constraint(constraint).0).collect();
+
+        let mut eliminated: Vec<_> = program
+            .eliminated
+            .into_iter()
+            .map(|eliminated| EliminatedVariable {
+                value: self.reconstruct_linear_combination(eliminated.value).0,
+                ..eliminated
+            })
+            .collect();
+        eliminated.extend(std::mem::take(&mut self.substitutions).into_values());
+        self.sources.clear();
+
+        (Program { imports: program.imports, constraints, eliminated, span: program.span }, Default::default())
+    }
+
+    fn reconstruct_constraint(&mut self, constraint: Constraint) -> (Option<Constraint>, Self::AdditionalOutput) {
+        // The constraint is also derived from the constraints that its substituted variables were eliminated with.
+        let sources: Vec<_> = [&constraint.a, &constraint.b, &constraint.c]
+            .into_iter()
+            .flat_map(|linear_combination| linear_combination.terms.iter())
+            .filter_map(|term| self.sources.get(&term.variable.name))
+            .flatten()
+            .copied()
+            .collect();
+
+        let substituted = [&constraint.a, &constraint.b, &constraint.c]
+            .into_iter()
+            .flat_map(|linear_combination| linear_combination.terms.iter())
+            .any(|term| self.substitutions.contains_key(&term.variable.name));
+
+        let Constraint { a, b, c, span, mut provenance } = constraint;
+        for source in sources {
+            provenance.record_source(source);
+        }
+        if substituted {
+            provenance.record_pass(Self::NAME);
+        }
+        let a = self.reconstruct_linear_combination(a).0;
+        let b = self.reconstruct_linear_combination(b).0;
+        let c = self.reconstruct_linear_combination(c).0;
+        (Some(Constraint { a, b, c, span, provenance }), Default::default())
+    }
+
+    fn reconstruct_linear_combination(
+        &mut self,
+        linear_combination: LinearCombination,
+    ) -> (LinearCombination, Self::AdditionalOutput) {
+        let substitutions = &self.substitutions;
+        (
+            substitute(linear_combination, |name| substitutions.get(&name).map(|eliminated| &eliminated.value)),
+            Default::default(),
+        )
+    }
+}
+
+/// Returns the linear combination `k * L - C` if the `constraint` is linear, i.e., states that `k * L - C = 0`.
+fn linear_difference(constraint: &Constraint) -> Option<LinearCombination> {
+    let (factor, other) = match (constraint.a.terms.is_empty(), constraint.b.terms.is_empty()) {
+        (true, _) => (&constraint.a.constant, &constraint.b),
+        (_, true) => (&constraint.b.constant, &constraint.a),
+        _ => return None,
+    };
+    let difference = other.clone() * factor.clone() - constraint.c.clone();
+    Some(Normalizer.reconstruct_linear_combination(difference).0)
+}
+
+/// Returns the `linear_combination` with each variable replaced by the value that `value_of` returns for it, if any.
+fn substitute<'a>(
+    linear_combination: LinearCombination,
+    value_of: impl Fn(Symbol) -> Option<&'a LinearCombination>,
+) -> LinearCombination {
+    let span = linear_combination.span;
+    let mut substituted = LinearCombination { terms: Vec::new(), constant: linear_combination.constant, span };
+    for term in linear_combination.terms {
+        match value_of(term.variable.name) {
+            Some(value) => substituted = substituted + value.clone() * term.constant,
+            None => substituted.terms.push(term),
+        }
+    }
+    // The substituted values come from elsewhere, so the linear combination keeps its own span.
+    substituted.span = span;
+    Normalizer.reconstruct_linear_combination(substituted).0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LinearEliminator, Normalizer, Pass};
+    use conlang_errors::emitter::Handler;
+    use conlang_span::{session_globals::create_session_if_not_set_then, span::BytePos};
+
+    /// Returns the program of `source` after normalization and linear elimination, keeping the `public_variables`.
+    fn eliminate(source: &str, public_variables: &[String]) -> String {
+        let program = conlang_parser::parse_program(&Handler::default(), source, BytePos(0)).unwrap();
+        LinearEliminator::do_pass((Normalizer::do_pass(program), public_variables)).to_string()
+    }
+
+    #[test]
+    fn test_eliminate() {
+        create_session_if_not_set_then(|_| {
+            // The variable that occurs in the fewest constraints is eliminated, and recorded with its value.
+            let source = "(x + y) * (1) = (z);\n(z) * (z) = (w);\n(2) * (w - x) = (v);\n";
+            assert_eq!(eliminate(source, &[]), "(z) * (z) = (w);\n// y = -1 * x + z\n// v = 2 * w - 2 * x\n");
+
+            // Public variables are kept, so a constraint over only public variables is kept as well.
+            let public = ["y".to_string(), "z".to_string()];
+            let expected = "(z) * (z) = (w);\n// x = -1 * y + z\n// v = 2 * w + 2 * y - 2 * z\n";
+            assert_eq!(eliminate(source, &public), expected);
+            let public = ["x".to_string(), "y".to_string(), "z".to_string()];
+            assert!(eliminate(source, &public).starts_with("(x + y) * (1) = (z);\n"));
+        });
+    }
+
+    #[test]
+    fn test_cancelled_variables() {
+        create_session_if_not_set_then(|_| {
+            // Once a variable is substituted, a constraint can hold for every assignment, or for none.
+            let source = "(x + y) * (1) = (z);\n(z) * (1) = (x + y);\n(1) * (z) = (1 + x + y);\n(2) * (3) = (7);\n";
+            let expected = "(0) * (1) = (0);\n(-1) * (1) = (0);\n(2) * (3) = (7);\n// x = -1 * y + z\n";
+            assert_eq!(eliminate(source, &[]), expected);
+        });
+    }
+
+    #[test]
+    fn test_provenance() {
+        create_session_if_not_set_then(|_| {
+            // `y` is eliminated as `x`, so `x` is eliminated as `z` with a constraint derived from both constraints.
+            let source = "(x) * (1) = (y);\n(y) * (1) = (z);\n(x) * (x) = (w);\n";
+            let program = conlang_parser::parse_program(&Handler::default(), source, BytePos(0)).unwrap();
+            let spans: Vec<_> = program.constraints.iter().map(|constraint| constraint.span).collect();
+            let public = ["w".to_string(), "z".to_string()];
+            let program = LinearEliminator::do_pass((Normalizer::do_pass(program), &public[..]));
+            assert_eq!(program.to_string(), "(z) * (z) = (w);\n// y = z\n// x = z\n");
+            assert_eq!(program.constraints[0].span, spans[2]);
+            assert_eq!(program.constraints[0].provenance.sources, [spans[1], spans[0]]);
+
+            // `y` is eliminated as `x`, whose elimination then rewrites the value of `y`.
+            let source = "(y) * (1) = (x);\n(x) * (1) = (z);\n(y) * (v) = (w);\n";
+            let program = conlang_parser::parse_program(&Handler::default(), source, BytePos(0)).unwrap();
+            let spans: Vec<_> = program.constraints.iter().map(|constraint| constraint.span).collect();
+            let public = ["v".to_string(), "w".to_string(), "z".to_string()];
+            let program = LinearEliminator::do_pass((Normalizer::do_pass(program), &public[..]));
+            assert_eq!(program.to_string(), "(z) * (v) = (w);\n// y = z\n// x = z\n");
+            assert_eq!(program.constraints[0].provenance.sources, spans[..2]);
+        });
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+mod normalizer;
+pub use normalizer::*;
+
+use crate::{Pass, Reconstructor};
+
+use conlang_ast::Program;
+
+impl Pass for Normalizer {
+    type Input = Program;
+    type Output = Program;
+
+    fn do_pass(program: Self::Input) -> Self::Output {
+        Normalizer.reconstruct(program)
+    }
+}
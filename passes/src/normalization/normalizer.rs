@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Reconstructor;
+
+use conlang_ast::*;
+use conlang_span::Symbol;
+
+use indexmap::{map::Entry, IndexMap};
+
+/// A `Normalizer` brings each linear combination of a program into a canonical form.
+/// Terms over the same variable are combined into a single term, and terms with a zero coefficient are removed.
+/// For example, `(x + 2 * y - x + y)` becomes `(3 * y)`.
+#[derive(Default)]
+pub struct Normalizer;
+
+impl Normalizer {
+    /// The name of the pass, which it records in the provenance of the constraints it changes.
+    pub const NAME: &'static str = "normalize";
+}
+
+impl Reconstructor for Normalizer {
+    type AdditionalOutput = ();
+
+    fn reconstruct_constraint(&mut self, constraint: Constraint) -> (Option<Constraint>, Self::AdditionalOutput) {
+        let Constraint { a, b, c, span, mut provenance } = constraint;
+        let terms = a.terms.len() + b.terms.len() + c.terms.len();
+        let a = self.reconstruct_linear_combination(a).0;
+        let b = self.reconstruct_linear_combination(b).0;
+        let c = self.reconstruct_linear_combination(c).0;
+        // Terms are only ever combined or removed, so the constraint changed if and only if it lost terms.
+        if a.terms.len() + b.terms.len() + c.terms.len() < terms {
+            provenance.record_pass(Self::NAME);
+        }
+        (Some(Constraint { a, b, c, span, provenance }), Default::default())
+    }
+
+    fn reconstruct_linear_combination(
+        &mut self,
+        linear_combination: LinearCombination,
+    ) -> (LinearCombination, Self::AdditionalOutput) {
+        // Combine the terms in order of the first occurrence of their variable.
+        let mut terms: IndexMap<Symbol, Term> = IndexMap::new();
+        for term in linear_combination.terms {
+            match terms.entry(term.variable.name) {
+                Entry::Occupied(mut entry) => {
+                    let constant = &mut entry.get_mut().constant;
+                    constant.value += term.constant.value;
+                    constant.repr = constant.value.to_string();
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(term);
+                }
+            }
+        }
+
+        (
+            LinearCombination {
+                terms: terms.into_values().filter(|term| !term.constant.is_zero()).collect(),
+                constant: linear_combination.constant,
+                span: linear_combination.span,
+            },
+            Default::default(),
+        )
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The built-in passes of the compiler pipeline.
+
+use crate::{ConstantFolder, Deduplicator, LinearEliminator, Normalizer, Pass, PassContext, ProgramPass};
+
+use conlang_ast::Program;
+use conlang_errors::Result;
+
+/// Runs the [`Normalizer`].
+pub struct NormalizationPass;
+
+impl ProgramPass for NormalizationPass {
+    fn name(&self) -> &'static str {
+        Normalizer::NAME
+    }
+
+    fn run(&self, program: Program, _context: &PassContext) -> Result<Program> {
+        Ok(Normalizer::do_pass(program))
+    }
+}
+
+/// Runs the [`LinearEliminator`].
+pub struct LinearEliminationPass;
+
+impl ProgramPass for LinearEliminationPass {
+    fn name(&self) -> &'static str {
+        LinearEliminator::NAME
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["normalize"]
+    }
+
+    fn run(&self, program: Program, context: &PassContext) -> Result<Program> {
+        Ok(LinearEliminator::do_pass((program, context.public_variables)))
+    }
+}
+
+/// Runs the [`ConstantFolder`].
+/// It runs after linear elimination, since substitution can leave constraints made only of constants.
+pub struct ConstantFoldingPass;
+
+impl ProgramPass for ConstantFoldingPass {
+    fn name(&self) -> &'static str {
+        ConstantFolder::NAME
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["normalize", "eliminate"]
+    }
+
+    fn run(&self, program: Program, context: &PassContext) -> Result<Program> {
+        ConstantFolder::do_pass((program, context.handler))
+    }
+}
+
+/// Runs the [`Deduplicator`].
+/// It runs last, since the other passes can make constraints equivalent.
+pub struct DeduplicationPass;
+
+impl ProgramPass for DeduplicationPass {
+    fn name(&self) -> &'static str {
+        Deduplicator::NAME
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["normalize", "eliminate", "fold"]
+    }
+
+    fn run(&self, program: Program, context: &PassContext) -> Result<Program> {
+        Ok(Deduplicator::do_pass((program, context.handler)))
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+//! A pass manager, which runs a configurable pipeline of passes over a program.
+
+mod builtin;
+pub use builtin::*;
+
+use conlang_ast::Program;
+use conlang_errors::{emitter::Handler, CompilerError, Result};
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// A pass that transforms a program, and can be registered with a [`PassManager`].
+///
+/// Passes outside of this crate implement this trait to be run as part of the compiler pipeline.
+///
+/// For example, a pass that runs after normalization:
+/// ```rust
+/// use conlang_ast::Program;
+/// use conlang_errors::Result;
+/// use conlang_passes::{PassContext, PassManager, ProgramPass};
+///
+/// struct Clear;
+///
+/// impl ProgramPass for Clear {
+///     fn name(&self) -> &'static str {
+///         "clear"
+///     }
+///
+///     fn dependencies(&self) -> &'static [&'static str] {
+///         &["normalize"]
+///     }
+///
+///     fn run(&self, _program: Program, _context: &PassContext) -> Result<Program> {
+///         Ok(Program::default())
+///     }
+/// }
+///
+/// let mut manager = PassManager::new();
+/// manager.register(Clear);
+///
+/// let pipeline = ["clear".to_string(), "normalize".to_string()];
+/// let order: Vec<_> = manager.schedule(&pipeline).unwrap().iter().map(|pass| pass.name()).collect();
+/// assert_eq!(order, ["normalize", "clear"]);
+/// ```
+pub trait ProgramPass: Send + Sync {
+    /// Returns the name of the pass, which selects it in a pipeline, e.g., `--passes=normalize,fold`.
+    fn name(&self) -> &'static str;
+
+    /// Returns the names of the passes that must run before this pass, if they are part of the pipeline.
+    fn dependencies(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Runs the pass on the `program`, reporting diagnostics through the `context`.
+    /// A pass that changes a constraint records its name in the [`Provenance`](conlang_ast::Provenance) of the
+    /// constraint, with [`Provenance::record_pass`](conlang_ast::Provenance::record_pass).
+    fn run(&self, program: Program, context: &PassContext) -> Result<Program>;
+}
+
+/// The information that passes share while running.
+pub struct PassContext<'a> {
+    /// The handler used to report errors and warnings.
+    pub handler: &'a Handler,
+    /// The public variables, which passes must keep in the program.
+    pub public_variables: &'a [String],
+}
+
+/// A summary of a pass that ran.
+#[derive(Clone, Debug)]
+pub struct PassReport {
+    /// The name of the pass.
+    pub name: &'static str,
+    /// The time the pass took.
+    pub duration: Duration,
+    /// The number of constraints after the pass.
+    pub constraints: usize,
+    /// The number of errors the pass emitted.
+    pub errors: usize,
+    /// The number of warnings the pass emitted.
+    pub warnings: usize,
+}
+
+/// A `PassManager` holds the registered passes, and runs a pipeline of them in an order that respects their dependencies.
+#[derive(Clone, Default)]
+pub struct PassManager {
+    /// The registered passes, in order of registration.
+    passes: Vec<Arc<dyn ProgramPass>>,
+}
+
+impl PassManager {
+    /// Returns a pass manager with the built-in passes registered.
+    pub fn new() -> Self {
+        let mut manager = Self::default();
+        manager
+            .register(NormalizationPass)
+            .register(LinearEliminationPass)
+            .register(ConstantFoldingPass)
+            .register(DeduplicationPass);
+        manager
+    }
+
+    /// Registers the `pass`, replacing a registered pass with the same name.
+    pub fn register(&mut self, pass: impl ProgramPass + 'static) -> &mut Self {
+        self.passes.retain(|registered| registered.name() != pass.name());
+        self.passes.push(Arc::new(pass));
+        self
+    }
+
+    /// Returns the names of the registered passes, in order of registration.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.passes.iter().map(|pass| pass.name())
+    }
+
+    /// Returns the passes named in the `pipeline`, ordered so that each pass runs after its dependencies.
+    /// Otherwise, the order of the pipeline is kept.
+    /// This function errors if a name is not registered, or if the dependencies form a cycle.
+    pub fn schedule(&self, pipeline: &[String]) -> Result<Vec<Arc<dyn ProgramPass>>> {
+        let mut pending = Vec::with_capacity(pipeline.len());
+        for name in pipeline {
+            let pass = self
+                .passes
+                .iter()
+                .find(|pass| pass.name() == name)
+                .ok_or_else(|| CompilerError::unknown_pass(name, self.names().collect::<Vec<_>>().join(", ")))?;
+            if !pending.iter().any(|pending: &Arc<dyn ProgramPass>| pending.name() == pass.name()) {
+                pending.push(pass.clone());
+            }
+        }
+
+        let mut scheduled: Vec<Arc<dyn ProgramPass>> = Vec::with_capacity(pending.len());
+        while !pending.is_empty() {
+            // The first pass whose dependencies in the pipeline have all been scheduled runs next.
+            let ready = pending.iter().position(|pass| {
+                pass.dependencies().iter().all(|dependency| {
+                    !pending.iter().any(|pending| pending.name() == *dependency)
+                        || scheduled.iter().any(|scheduled| scheduled.name() == *dependency)
+                })
+            });
+            match ready {
+                Some(index) => scheduled.push(pending.remove(index)),
+                None => {
+                    let names: Vec<_> = pending.iter().map(|pass| pass.name()).collect();
+                    return Err(CompilerError::pass_dependency_cycle(names.join(", ")).into());
+                }
+            }
+        }
+        Ok(scheduled)
+    }
+
+    /// Runs the passes of the `pipeline` on the `program`, calling `after_pass` with each pass and its result.
+    /// Returns the transformed program, and a report for each pass.
+    pub fn run(
+        &self,
+        pipeline: &[String],
+        mut program: Program,
+        context: &PassContext,
+        mut after_pass: impl FnMut(&dyn ProgramPass, &Program) -> Result<()>,
+    ) -> Result<(Program, Vec<PassReport>)> {
+        let mut reports = Vec::new();
+        for pass in self.schedule(pipeline)? {
+            let (errors, warnings) = (context.handler.err_count(), context.handler.warning_count());
+            let start = Instant::now();
+
+            program = pass.run(program, context)?;
+
+            reports.push(PassReport {
+                name: pass.name(),
+                duration: start.elapsed(),
+                constraints: program.constraints.len(),
+                errors: context.handler.err_count() - errors,
+                warnings: context.handler.warning_count() - warnings,
+            });
+
+            after_pass(pass.as_ref(), &program)?;
+        }
+        Ok((program, reports))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conlang_span::{session_globals::create_session_if_not_set_then, span::BytePos};
+
+    /// A pass that depends on the passes named `dependencies`, and leaves the program as it is.
+    struct Identity(&'static str, &'static [&'static str]);
+
+    impl ProgramPass for Identity {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn dependencies(&self) -> &'static [&'static str] {
+            self.1
+        }
+
+        fn run(&self, program: Program, _context: &PassContext) -> Result<Program> {
+            Ok(program)
+        }
+    }
+
+    /// Returns the names of the passes of the `pipeline`, in the order that the `manager` schedules them.
+    fn schedule(manager: &PassManager, pipeline: &[&str]) -> Result<Vec<&'static str>> {
+        let pipeline: Vec<_> = pipeline.iter().map(|name| name.to_string()).collect();
+        Ok(manager.schedule(&pipeline)?.iter().map(|pass| pass.name()).collect())
+    }
+
+    #[test]
+    fn test_schedule() {
+        let mut manager = PassManager::new();
+        assert_eq!(manager.names().collect::<Vec<_>>(), ["normalize", "eliminate", "fold", "dedup"]);
+
+        // Passes run after their dependencies in the pipeline, and otherwise in the order of the pipeline.
+        assert_eq!(schedule(&manager, &["dedup", "fold", "normalize"]).unwrap(), ["normalize", "fold", "dedup"]);
+        assert_eq!(schedule(&manager, &["dedup", "dedup"]).unwrap(), ["dedup"]);
+        assert!(schedule(&manager, &[]).unwrap().is_empty());
+
+        // A registered pass replaces the pass with the same name.
+        manager.register(Identity("fold", &["dedup"])).register(Identity("custom", &["fold"]));
+        assert_eq!(manager.names().collect::<Vec<_>>(), ["normalize", "eliminate", "dedup", "fold", "custom"]);
+        assert_eq!(schedule(&manager, &["custom", "fold", "normalize"]).unwrap(), ["fold", "custom", "normalize"]);
+
+        let err = schedule(&manager, &["fold", "dedup"]).unwrap_err();
+        assert_eq!(err.message(), "The dependencies of the passes fold, dedup form a cycle.");
+        let err = schedule(&manager, &["normalize", "unknown"]).unwrap_err();
+        assert_eq!(err.message(), "Unknown pass `unknown`.");
+    }
+
+    #[test]
+    fn test_run() {
+        create_session_if_not_set_then(|_| {
+            let (handler, _) = Handler::new_with_buf();
+            let source = "(x + x) * (1) = (y);\n(1) * (2 * x) = (y);\n(2) * (3) = (6);\n";
+            let program = conlang_parser::parse_program(&handler, source, BytePos(0)).unwrap();
+            let context = PassContext { handler: &handler, public_variables: &[] };
+
+            let mut after = Vec::new();
+            let pipeline = ["dedup".to_string(), "fold".to_string(), "normalize".to_string()];
+            let (program, reports) = PassManager::new()
+                .run(&pipeline, program, &context, |pass, program| {
+                    after.push((pass.name(), program.constraints.len()));
+                    Ok(())
+                })
+                .unwrap();
+
+            assert_eq!(after, [("normalize", 3), ("fold", 2), ("dedup", 1)]);
+            let reports: Vec<_> =
+                reports.iter().map(|report| (report.name, report.constraints, report.warnings)).collect();
+            assert_eq!(reports, [("normalize", 3, 0), ("fold", 2, 0), ("dedup", 1, 1)]);
+            // Only the passes that changed the constraint are recorded in its provenance.
+            assert_eq!(program.to_string(), "(2 * x) * (1) = (y);\n");
+            assert_eq!(program.constraints[0].provenance.to_string(), "changed by normalize");
+        });
+    }
+
+    #[test]
+    fn test_provenance() {
+        create_session_if_not_set_then(|_| {
+            let (handler, _) = Handler::new_with_buf();
+            let source = "(x + x) * (y) = (z);\n(1) * (y) = (w);\n(1) * (w) = (v);\n(u) * (u) = (1);\n";
+            let program = conlang_parser::parse_program(&handler, source, BytePos(0)).unwrap();
+            let public_variables = ["x".to_string(), "z".to_string(), "w".to_string(), "v".to_string()];
+            let context = PassContext { handler: &handler, public_variables: &public_variables };
+
+            let pipeline: Vec<_> = PassManager::new().names().map(|name| name.to_string()).collect();
+            let (program, _) = PassManager::new().run(&pipeline, program, &context, |_, _| Ok(())).unwrap();
+
+            // Each pass records itself in the constraints it changed, and only in those.
+            assert_eq!(program.to_string(), "(2 * x) * (w) = (z);\n(w) * (1) = (v);\n(u) * (u) = (1);\n// y = w\n");
+            let passes: Vec<_> =
+                program.constraints.iter().map(|constraint| constraint.provenance.passes.join(", ")).collect();
+            assert_eq!(passes, ["normalize, eliminate", "fold", ""]);
+        });
+    }
+}
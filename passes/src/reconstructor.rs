@@ -14,36 +14,115 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::Combine;
+
 use conlang_ast::*;
 
 /// A Reconstructor trait for reconstructing the AST.
+///
+/// Each method returns the reconstructed node, and an additional output.
+/// By default, a node is reconstructed from its reconstructed children, and its additional output
+/// is the [`Combine::combine`] of theirs.
+/// A constraint may also be removed from the program, by reconstructing it into `None`.
+/// For example, the following removes the constraints that use a variable, and counts them:
+/// ```rust
+/// use conlang_ast::{Constraint, ProgramBuilder};
+/// use conlang_passes::Reconstructor;
+///
+/// struct Remover(&'static str);
+///
+/// impl Reconstructor for Remover {
+///     type AdditionalOutput = usize;
+///
+///     fn reconstruct_constraint(&mut self, constraint: Constraint) -> (Option<Constraint>, usize) {
+///         let uses = [&constraint.a, &constraint.b, &constraint.c]
+///             .into_iter()
+///             .any(|linear_combination| linear_combination.terms.iter().any(|term| term.variable.name.as_str() == self.0));
+///         match uses {
+///             true => (None, 1),
+///             false => (Some(constraint), 0),
+///         }
+///     }
+/// }
+///
+/// # conlang_span::session_globals::create_session_if_not_set_then(|_| {
+/// let mut builder = ProgramBuilder::new();
+/// let (x, y, z) = (builder.var("x"), builder.var("y"), builder.var("z"));
+/// builder.enforce(x.clone(), y.clone(), z.clone()).enforce(z.clone(), z.clone(), y).enforce(x.clone(), x, z);
+///
+/// let (program, removed) = Remover("y").reconstruct_program(builder.build());
+/// assert_eq!(program.constraints.len(), 1);
+/// assert_eq!(removed, 2);
+/// # });
+/// ```
 pub trait Reconstructor {
-    type AdditionalOutput: Default;
+    type AdditionalOutput: Combine;
+
+    /// Reconstructs the `program`, discarding the additional output.
+    fn reconstruct(&mut self, program: Program) -> Program {
+        self.reconstruct_program(program).0
+    }
 
     /// Reconstruct a program.
     fn reconstruct_program(&mut self, program: Program) -> (Program, Self::AdditionalOutput) {
-        (
-            Program {
-                constraints: program
-                    .constraints
-                    .into_iter()
-                    .map(|constraint| self.reconstruct_constraint(constraint).0)
-                    .collect(),
-            },
-            Default::default(),
-        )
+        let mut output = Self::AdditionalOutput::default();
+
+        let mut imports = Vec::with_capacity(program.imports.len());
+        for import in program.imports {
+            let (import, import_output) = self.reconstruct_import(import);
+            imports.push(import);
+            output = output.combine(import_output);
+        }
+
+        let mut constraints = Vec::with_capacity(program.constraints.len());
+        for constraint in program.constraints {
+            let (constraint, constraint_output) = self.reconstruct_constraint(constraint);
+            constraints.extend(constraint);
+            output = output.combine(constraint_output);
+        }
+
+        let mut eliminated = Vec::with_capacity(program.eliminated.len());
+        for variable in program.eliminated {
+            let (variable, variable_output) = self.reconstruct_eliminated_variable(variable);
+            eliminated.push(variable);
+            output = output.combine(variable_output);
+        }
+
+        (Program { imports, constraints, eliminated, span: program.span }, output)
+    }
+
+    /// Reconstruct an import.
+    /// Only the variables of the importing program, i.e., those that the ports are connected to, are reconstructed.
+    fn reconstruct_import(&mut self, import: Import) -> (Import, Self::AdditionalOutput) {
+        let mut output = Self::AdditionalOutput::default();
+
+        let mut ports = Vec::with_capacity(import.ports.len());
+        for port in import.ports {
+            let (outer, outer_output) = self.reconstruct_variable(port.outer);
+            ports.push(Port { inner: port.inner, outer });
+            output = output.combine(outer_output);
+        }
+
+        (Import { ports, ..import }, output)
+    }
+
+    /// Reconstruct a constraint, or remove it from the program by returning `None`.
+    fn reconstruct_constraint(&mut self, constraint: Constraint) -> (Option<Constraint>, Self::AdditionalOutput) {
+        let (a, a_output) = self.reconstruct_linear_combination(constraint.a);
+        let (b, b_output) = self.reconstruct_linear_combination(constraint.b);
+        let (c, c_output) = self.reconstruct_linear_combination(constraint.c);
+        let constraint = Constraint { a, b, c, span: constraint.span, provenance: constraint.provenance };
+        (Some(constraint), a_output.combine(b_output).combine(c_output))
     }
 
-    /// Reconstruct a constraint.
-    fn reconstruct_constraint(&mut self, constraint: Constraint) -> (Constraint, Self::AdditionalOutput) {
-        (
-            Constraint {
-                a: self.reconstruct_linear_combination(constraint.a).0,
-                b: self.reconstruct_linear_combination(constraint.b).0,
-                c: self.reconstruct_linear_combination(constraint.c).0,
-            },
-            Default::default(),
-        )
+    /// Reconstruct a variable that was eliminated from the constraints.
+    fn reconstruct_eliminated_variable(
+        &mut self,
+        eliminated: EliminatedVariable,
+    ) -> (EliminatedVariable, Self::AdditionalOutput) {
+        let (variable, variable_output) = self.reconstruct_variable(eliminated.variable);
+        let (value, value_output) = self.reconstruct_linear_combination(eliminated.value);
+        (EliminatedVariable { variable, value, span: eliminated.span }, variable_output.combine(value_output))
     }
 
     /// Reconstruct a linear combination.
@@ -51,24 +130,24 @@ pub trait Reconstructor {
         &mut self,
         linear_combination: LinearCombination,
     ) -> (LinearCombination, Self::AdditionalOutput) {
-        (
-            LinearCombination {
-                terms: linear_combination.terms.into_iter().map(|term| self.reconstruct_term(term).0).collect(),
-                constant: self.reconstruct_constant(linear_combination.constant).0,
-            },
-            Default::default(),
-        )
+        let mut output = Self::AdditionalOutput::default();
+
+        let mut terms = Vec::with_capacity(linear_combination.terms.len());
+        for term in linear_combination.terms {
+            let (term, term_output) = self.reconstruct_term(term);
+            terms.push(term);
+            output = output.combine(term_output);
+        }
+        let (constant, constant_output) = self.reconstruct_constant(linear_combination.constant);
+
+        (LinearCombination { terms, constant, span: linear_combination.span }, output.combine(constant_output))
     }
 
     /// Reconstruct a term.
     fn reconstruct_term(&mut self, term: Term) -> (Term, Self::AdditionalOutput) {
-        (
-            Term {
-                constant: self.reconstruct_constant(term.constant).0,
-                variable: self.reconstruct_variable(term.variable).0,
-            },
-            Default::default(),
-        )
+        let (constant, constant_output) = self.reconstruct_constant(term.constant);
+        let (variable, variable_output) = self.reconstruct_variable(term.variable);
+        (Term { constant, variable, span: term.span }, constant_output.combine(variable_output))
     }
 
     /// Reconstruct a variable.
@@ -81,3 +160,54 @@ pub trait Reconstructor {
         (constant, Default::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conlang_span::{session_globals::create_session_if_not_set_then, span::BytePos, Span, Symbol};
+    use indexmap::IndexSet;
+
+    /// Renames the variables called `x` to `w`, collecting the renamed spans,
+    /// and removes the constraints without variables, counting them.
+    struct Renamer;
+
+    impl Reconstructor for Renamer {
+        type AdditionalOutput = (IndexSet<Span>, usize);
+
+        fn reconstruct_constraint(&mut self, constraint: Constraint) -> (Option<Constraint>, Self::AdditionalOutput) {
+            let has_variables = [&constraint.a, &constraint.b, &constraint.c].iter().any(|side| !side.terms.is_empty());
+            let Constraint { a, b, c, span, provenance } = constraint;
+            let (a, a_output) = self.reconstruct_linear_combination(a);
+            let (b, b_output) = self.reconstruct_linear_combination(b);
+            let (c, c_output) = self.reconstruct_linear_combination(c);
+            let output = a_output.combine(b_output).combine(c_output);
+            match has_variables {
+                true => (Some(Constraint { a, b, c, span, provenance }), output),
+                false => (None, output.combine((IndexSet::new(), 1))),
+            }
+        }
+
+        fn reconstruct_variable(&mut self, variable: Variable) -> (Variable, Self::AdditionalOutput) {
+            match &*variable.name.as_str() {
+                "x" => (Variable { name: Symbol::intern("w"), ..variable }, (IndexSet::from([variable.span]), 0)),
+                _ => (variable, Default::default()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_additional_output() {
+        create_session_if_not_set_then(|_| {
+            let source = "(x) * (2) = (y);\n(2) * (3) = (6);\n(y) * (y) = (x + x);\n(1) * (1) = (1);\n";
+            let mut program = conlang_parser::parse_program(&Default::default(), source, BytePos(0)).unwrap();
+            let value = LinearCombination::from(Term::from(Variable::new("x")));
+            program.eliminated.push(EliminatedVariable { variable: Variable::new("z"), value, span: Span::dummy() });
+
+            // The outputs of the removed constraints and of the eliminated variables are combined as well.
+            let (program, (renamed, removed)) = Renamer.reconstruct_program(program);
+            assert_eq!(program.to_string(), "(w) * (2) = (y);\n(y) * (y) = (w + w);\n// z = w\n");
+            assert_eq!(renamed.len(), 4);
+            assert_eq!(removed, 2);
+        });
+    }
+}
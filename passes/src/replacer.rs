@@ -17,23 +17,34 @@
 use crate::Reconstructor;
 use conlang_ast::Variable;
 
-/// A `Replacer` applies `replacer` to all `Variables`s in an AST.
+/// A `Replacer` applies `replacer` to all `Variables`s in an AST, including the variables that imports connect to.
 ///
 /// `Replacer`s can be used to rename variable.
 ///  To do so,
 ///    1. Create a `Replacer` with a closure that returns a new `Variable`.
 ///    2. Call `reconstruct` on the `Replacer` with the AST to be renamed.
+///
 ///  For example,
-/// ```rust,no_run
-///     use conlang_ast::{Program, Variable};
-///     use conlang_passes::Replacer;
+/// ```rust
+/// use conlang_ast::{Constant, Constraint, Import, LinearCombination, Program, Term, Variable};
+/// use conlang_passes::{Reconstructor, Replacer};
+///
+/// # conlang_span::session_globals::create_session_if_not_set_then(|_| {
+/// let mut replacer = Replacer::new(|variable| Variable::new(format!("{}_renamed", variable.name)));
+///
+/// let mut program = Program::default();
+/// let x = LinearCombination::from_terms([Term::new(Constant::from(2), Variable::new("x"))]);
+/// program.constraints.push(Constraint::new(x.clone(), Variable::new("y").into(), x));
+/// // The variables of the imported program keep their names.
+/// program.imports.push(Import::new("square", "sq").with_port("input", "x"));
 ///
-///     let replacer = Replacer::new(|variable| {
-///        Variable::new(format!("{}_renamed", variable.name))
-///    });
-///    let program = Program::default();
-///    let renamed_program = replacer.reconstruct(program);
-///  ```
+/// let renamed_program = replacer.reconstruct(program);
+/// assert_eq!(
+///     renamed_program.to_string(),
+///     "import square as sq (input = x_renamed);\n(2 * x_renamed) * (y_renamed) = (2 * x_renamed);\n"
+/// );
+/// # });
+/// ```
 pub struct Replacer<F>
 where
     F: Fn(&Variable) -> Variable,
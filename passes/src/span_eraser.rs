@@ -0,0 +1,38 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::MutVisitor;
+use conlang_ast::Provenance;
+use conlang_span::Span;
+
+/// A `SpanEraser` replaces every span in an AST with [`Span::dummy`].
+///
+/// Spans are byte positions in the source map of the session that created them.
+/// An AST loaded from elsewhere, e.g., from JSON, has to be erased before it is used,
+/// so that its spans do not point into unrelated sources.
+#[derive(Default)]
+pub struct SpanEraser;
+
+impl MutVisitor for SpanEraser {
+    /// The other sources of a constraint are only recorded with a source location, so they are removed.
+    fn visit_provenance_mut(&mut self, provenance: &mut Provenance) {
+        provenance.sources.clear();
+    }
+
+    fn visit_span_mut(&mut self, span: &mut Span) {
+        *span = Span::dummy();
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CircuitStatistics, Visitor};
+
+use conlang_ast::*;
+use conlang_span::Symbol;
+
+use indexmap::{IndexMap, IndexSet};
+
+/// A `StatisticsCollector` traverses a program and measures the size of its circuit.
+#[derive(Default)]
+pub struct StatisticsCollector<'a> {
+    /// The statistics collected so far.
+    statistics: CircuitStatistics,
+    /// The index of the constraint being visited.
+    constraint_index: usize,
+    /// The non-zero entries of each linear combination in the constraint being visited.
+    widths: Vec<usize>,
+    /// The constraints each variable occurs in.
+    occurrences: IndexMap<&'a Symbol, IndexSet<usize>>,
+}
+
+impl<'a> StatisticsCollector<'a> {
+    /// Returns the collected statistics.
+    pub fn finish(mut self) -> CircuitStatistics {
+        self.statistics.variables = self.occurrences.len();
+        for constraints in self.occurrences.values() {
+            *self.statistics.degree_histogram.entry(constraints.len()).or_default() += 1;
+        }
+        self.statistics
+    }
+}
+
+impl<'a> Visitor<'a> for StatisticsCollector<'a> {
+    type AdditionalInput = ();
+    type Output = ();
+
+    fn visit_constraint(&mut self, constraint: &'a Constraint, additional_input: &Self::AdditionalInput) {
+        self.widths.clear();
+        self.visit_linear_combination(&constraint.a, additional_input);
+        self.visit_linear_combination(&constraint.b, additional_input);
+        self.visit_linear_combination(&constraint.c, additional_input);
+
+        self.statistics.nonzero_a += self.widths[0];
+        self.statistics.nonzero_b += self.widths[1];
+        self.statistics.nonzero_c += self.widths[2];
+        if !has_variables(&constraint.a) || !has_variables(&constraint.b) {
+            self.statistics.linear_constraints += 1;
+        }
+
+        self.statistics.constraints += 1;
+        self.constraint_index += 1;
+    }
+
+    /// The ports of imports are not part of the circuit.
+    fn visit_import(&mut self, _import: &'a Import, _additional_input: &()) {}
+
+    /// Eliminated variables are not part of the circuit.
+    fn visit_eliminated_variable(&mut self, _eliminated: &'a EliminatedVariable, _additional_input: &()) {}
+
+    fn visit_linear_combination(
+        &mut self,
+        linear_combination: &'a LinearCombination,
+        additional_input: &Self::AdditionalInput,
+    ) {
+        // A variable with a zero coefficient does not occur in the circuit.
+        for term in linear_combination.terms.iter().filter(|term| !term.constant.is_zero()) {
+            self.visit_term(term, additional_input);
+        }
+
+        // The constant is the coefficient of the variable that is always one.
+        let mut width = linear_combination.terms.iter().filter(|term| !term.constant.is_zero()).count();
+        if !linear_combination.constant.is_zero() {
+            width += 1;
+        }
+        self.statistics.max_linear_combination_width = self.statistics.max_linear_combination_width.max(width);
+        self.widths.push(width);
+    }
+
+    fn visit_variable(&mut self, variable: &'a Variable, _additional_input: &Self::AdditionalInput) {
+        self.occurrences.entry(&variable.name).or_default().insert(self.constraint_index);
+    }
+}
+
+/// Returns whether the `linear_combination` has a variable with a non-zero coefficient.
+fn has_variables(linear_combination: &LinearCombination) -> bool {
+    linear_combination.terms.iter().any(|term| !term.constant.is_zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Pass, StatisticsCollector};
+    use conlang_errors::emitter::Handler;
+    use conlang_span::{session_globals::create_session_if_not_set_then, span::BytePos};
+
+    #[test]
+    fn test_zero_coefficients() {
+        create_session_if_not_set_then(|_| {
+            let handler = Handler::default();
+            let source = "(0 * x + y) * (z) = (w);\n(0 * z) * (y) = (1);\n";
+            let program = conlang_parser::parse_program(&handler, source, BytePos(0)).unwrap();
+            let statistics = StatisticsCollector::do_pass(&program);
+
+            // A variable with a zero coefficient neither occurs in the circuit nor makes a constraint quadratic.
+            assert_eq!(statistics.constraints, 2);
+            assert_eq!(statistics.variables, 3);
+            assert_eq!((statistics.nonzero_a, statistics.nonzero_b, statistics.nonzero_c), (1, 2, 2));
+            assert_eq!(statistics.linear_constraints, 1);
+            assert_eq!(statistics.max_linear_combination_width, 1);
+            assert_eq!(statistics.degree_histogram.into_iter().collect::<Vec<_>>(), [(1, 2), (2, 1)]);
+        });
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+mod collector;
+pub use collector::*;
+
+use crate::{Pass, Visitor};
+
+use conlang_ast::Program;
+
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt};
+
+/// Size measurements of a circuit, used to track how changes affect it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CircuitStatistics {
+    /// The number of constraints.
+    pub constraints: usize,
+    /// The number of distinct variables.
+    pub variables: usize,
+    /// The number of non-zero entries in the `A` matrix.
+    pub nonzero_a: usize,
+    /// The number of non-zero entries in the `B` matrix.
+    pub nonzero_b: usize,
+    /// The number of non-zero entries in the `C` matrix.
+    pub nonzero_c: usize,
+    /// The number of constraints where `A` or `B` is a constant, making the constraint linear.
+    pub linear_constraints: usize,
+    /// The largest number of non-zero entries in a single linear combination.
+    pub max_linear_combination_width: usize,
+    /// Maps each degree, i.e., the number of constraints a variable occurs in, to the number of variables with it.
+    pub degree_histogram: BTreeMap<usize, usize>,
+}
+
+impl fmt::Display for CircuitStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows = [
+            ("Constraints", self.constraints),
+            ("Variables", self.variables),
+            ("Non-zero entries in A", self.nonzero_a),
+            ("Non-zero entries in B", self.nonzero_b),
+            ("Non-zero entries in C", self.nonzero_c),
+            ("Linear constraints", self.linear_constraints),
+            ("Max linear combination width", self.max_linear_combination_width),
+        ];
+        for (name, value) in rows {
+            writeln!(f, "{name:<30}{value:>10}")?;
+        }
+
+        writeln!(f, "\n{:<30}{:>10}", "Degree", "Variables")?;
+        for (degree, count) in self.degree_histogram.iter() {
+            writeln!(f, "{degree:<30}{count:>10}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Pass for StatisticsCollector<'a> {
+    type Input = &'a Program;
+    type Output = CircuitStatistics;
+
+    fn do_pass(program: Self::Input) -> Self::Output {
+        let mut visitor = StatisticsCollector::default();
+        visitor.visit_program(program, &());
+        visitor.finish()
+    }
+}
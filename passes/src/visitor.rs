@@ -14,19 +14,64 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::Combine;
+
 use conlang_ast::*;
+use conlang_span::Span;
 
 /// A Visitor trait for traversing the AST.
+///
+/// By default, a node is visited by visiting its children, and combining their outputs with [`Combine::combine`].
+/// An analysis only overrides the methods for the nodes it inspects.
+/// For example, the following collects the names of the variables in a program:
+/// ```rust
+/// use conlang_ast::{Program, ProgramBuilder, Variable};
+/// use conlang_passes::Visitor;
+/// use conlang_span::SymbolStr;
+///
+/// struct VariableNames;
+///
+/// impl<'a> Visitor<'a> for VariableNames {
+///     type AdditionalInput = ();
+///     type Output = Vec<SymbolStr>;
+///
+///     fn visit_variable(&mut self, variable: &'a Variable, _additional_input: &()) -> Self::Output {
+///         vec![variable.name.as_str()]
+///     }
+/// }
+///
+/// # conlang_span::session_globals::create_session_if_not_set_then(|_| {
+/// let mut builder = ProgramBuilder::new();
+/// let (x, y) = (builder.var("x"), builder.var("y"));
+/// builder.enforce(x.clone(), y.clone(), x + y);
+/// let program = builder.build();
+///
+/// assert_eq!(VariableNames.visit_program(&program, &()), ["x", "y", "x", "y"]);
+/// # });
+/// ```
 pub trait Visitor<'a> {
     type AdditionalInput: Default;
-    type Output: Default;
+    type Output: Combine;
 
     /// Visit a program.
     fn visit_program(&mut self, program: &'a Program, additional_input: &Self::AdditionalInput) -> Self::Output {
-        for constraint in &program.constraints {
-            self.visit_constraint(constraint, additional_input);
-        }
-        Default::default()
+        let imports =
+            Self::Output::combine_all(program.imports.iter().map(|import| self.visit_import(import, additional_input)));
+        let constraints = Self::Output::combine_all(
+            program.constraints.iter().map(|constraint| self.visit_constraint(constraint, additional_input)),
+        );
+        let eliminated = Self::Output::combine_all(
+            program.eliminated.iter().map(|eliminated| self.visit_eliminated_variable(eliminated, additional_input)),
+        );
+        imports.combine(constraints).combine(eliminated)
+    }
+
+    /// Visit an import.
+    fn visit_import(&mut self, import: &'a Import, additional_input: &Self::AdditionalInput) -> Self::Output {
+        Self::Output::combine_all(import.ports.iter().map(|port| {
+            self.visit_variable(&port.inner, additional_input)
+                .combine(self.visit_variable(&port.outer, additional_input))
+        }))
     }
 
     /// Visit a constraint.
@@ -35,10 +80,19 @@ pub trait Visitor<'a> {
         constraint: &'a Constraint,
         additional_input: &Self::AdditionalInput,
     ) -> Self::Output {
-        self.visit_linear_combination(&constraint.a, additional_input);
-        self.visit_linear_combination(&constraint.b, additional_input);
-        self.visit_linear_combination(&constraint.c, additional_input);
-        Default::default()
+        self.visit_linear_combination(&constraint.a, additional_input)
+            .combine(self.visit_linear_combination(&constraint.b, additional_input))
+            .combine(self.visit_linear_combination(&constraint.c, additional_input))
+    }
+
+    /// Visit a variable that was eliminated from the constraints.
+    fn visit_eliminated_variable(
+        &mut self,
+        eliminated: &'a EliminatedVariable,
+        additional_input: &Self::AdditionalInput,
+    ) -> Self::Output {
+        self.visit_variable(&eliminated.variable, additional_input)
+            .combine(self.visit_linear_combination(&eliminated.value, additional_input))
     }
 
     /// Visit a linear combination.
@@ -47,18 +101,16 @@ pub trait Visitor<'a> {
         linear_combination: &'a LinearCombination,
         additional_input: &Self::AdditionalInput,
     ) -> Self::Output {
-        for term in &linear_combination.terms {
-            self.visit_term(term, additional_input);
-        }
-        self.visit_constant(&linear_combination.constant, additional_input);
-        Default::default()
+        let terms = Self::Output::combine_all(
+            linear_combination.terms.iter().map(|term| self.visit_term(term, additional_input)),
+        );
+        terms.combine(self.visit_constant(&linear_combination.constant, additional_input))
     }
 
     /// Visit a term.
     fn visit_term(&mut self, term: &'a Term, additional_input: &Self::AdditionalInput) -> Self::Output {
-        self.visit_constant(&term.constant, additional_input);
-        self.visit_variable(&term.variable, additional_input);
-        Default::default()
+        self.visit_constant(&term.constant, additional_input)
+            .combine(self.visit_variable(&term.variable, additional_input))
     }
 
     /// Visit a variable.
@@ -71,3 +123,156 @@ pub trait Visitor<'a> {
         Default::default()
     }
 }
+
+/// A MutVisitor trait for editing the AST in place.
+///
+/// Unlike a [`Reconstructor`](crate::Reconstructor), it does not rebuild the nodes it passes through,
+/// so it suits edits that keep the shape of the tree, such as renaming variables or rewriting spans.
+pub trait MutVisitor {
+    /// Visit a program.
+    fn visit_program_mut(&mut self, program: &mut Program) {
+        for import in program.imports.iter_mut() {
+            self.visit_import_mut(import);
+        }
+        for constraint in program.constraints.iter_mut() {
+            self.visit_constraint_mut(constraint);
+        }
+        for eliminated in program.eliminated.iter_mut() {
+            self.visit_eliminated_variable_mut(eliminated);
+        }
+        self.visit_span_mut(&mut program.span);
+    }
+
+    /// Visit an import.
+    fn visit_import_mut(&mut self, import: &mut Import) {
+        for port in import.ports.iter_mut() {
+            self.visit_variable_mut(&mut port.inner);
+            self.visit_variable_mut(&mut port.outer);
+        }
+        self.visit_span_mut(&mut import.span);
+    }
+
+    /// Visit a constraint.
+    fn visit_constraint_mut(&mut self, constraint: &mut Constraint) {
+        self.visit_linear_combination_mut(&mut constraint.a);
+        self.visit_linear_combination_mut(&mut constraint.b);
+        self.visit_linear_combination_mut(&mut constraint.c);
+        self.visit_provenance_mut(&mut constraint.provenance);
+        self.visit_span_mut(&mut constraint.span);
+    }
+
+    /// Visit the provenance of a constraint.
+    fn visit_provenance_mut(&mut self, provenance: &mut Provenance) {
+        for source in provenance.sources.iter_mut() {
+            self.visit_span_mut(source);
+        }
+    }
+
+    /// Visit a variable that was eliminated from the constraints.
+    fn visit_eliminated_variable_mut(&mut self, eliminated: &mut EliminatedVariable) {
+        self.visit_variable_mut(&mut eliminated.variable);
+        self.visit_linear_combination_mut(&mut eliminated.value);
+        self.visit_span_mut(&mut eliminated.span);
+    }
+
+    /// Visit a linear combination.
+    fn visit_linear_combination_mut(&mut self, linear_combination: &mut LinearCombination) {
+        for term in linear_combination.terms.iter_mut() {
+            self.visit_term_mut(term);
+        }
+        self.visit_constant_mut(&mut linear_combination.constant);
+        self.visit_span_mut(&mut linear_combination.span);
+    }
+
+    /// Visit a term.
+    fn visit_term_mut(&mut self, term: &mut Term) {
+        self.visit_constant_mut(&mut term.constant);
+        self.visit_variable_mut(&mut term.variable);
+        self.visit_span_mut(&mut term.span);
+    }
+
+    /// Visit a variable.
+    fn visit_variable_mut(&mut self, variable: &mut Variable) {
+        self.visit_span_mut(&mut variable.span);
+    }
+
+    /// Visit a constant.
+    fn visit_constant_mut(&mut self, constant: &mut Constant) {
+        self.visit_span_mut(&mut constant.span);
+    }
+
+    /// Visit the span of a node.
+    fn visit_span_mut(&mut self, _span: &mut Span) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conlang_span::{session_globals::create_session_if_not_set_then, span::BytePos, Symbol, SymbolStr};
+    use indexmap::IndexMap;
+
+    /// Counts the occurrences of each variable, and the constants that are not zero.
+    struct Counter;
+
+    impl<'a> Visitor<'a> for Counter {
+        type AdditionalInput = ();
+        type Output = (IndexMap<SymbolStr, usize>, usize);
+
+        fn visit_variable(&mut self, variable: &'a Variable, _additional_input: &()) -> Self::Output {
+            (IndexMap::from([(variable.name.as_str(), 1)]), 0)
+        }
+
+        fn visit_constant(&mut self, constant: &'a Constant, _additional_input: &()) -> Self::Output {
+            (IndexMap::new(), usize::from(!constant.is_zero()))
+        }
+    }
+
+    /// Renames the variables called `x` to `w`, and erases the spans.
+    struct Renamer;
+
+    impl MutVisitor for Renamer {
+        fn visit_variable_mut(&mut self, variable: &mut Variable) {
+            if variable.name.as_str() == "x" {
+                variable.name = Symbol::intern("w");
+            }
+            self.visit_span_mut(&mut variable.span);
+        }
+
+        fn visit_span_mut(&mut self, span: &mut Span) {
+            *span = Span::dummy();
+        }
+    }
+
+    /// Returns the program of `source`, with the variable `y` eliminated as `y = 2 * x`.
+    fn program(source: &str) -> Program {
+        let mut program = conlang_parser::parse_program(&Default::default(), source, BytePos(0)).unwrap();
+        let value = LinearCombination::from(Constant::from(2) * Term::from(Variable::new("x")));
+        program.eliminated.push(EliminatedVariable { variable: Variable::new("y"), value, span: Span::dummy() });
+        program
+    }
+
+    #[test]
+    fn test_visitor() {
+        create_session_if_not_set_then(|_| {
+            let program = program("import square as s (input = x);\n(x + z) * (3 * z) = (1 + x);\n");
+
+            // The outputs of the children are combined, in order, including those of imports and eliminated variables.
+            let (occurrences, constants) = Counter.visit_program(&program, &());
+            let occurrences: Vec<_> = occurrences.iter().map(|(name, count)| format!("{name}: {count}")).collect();
+            assert_eq!(occurrences, ["input: 1", "x: 4", "z: 2", "y: 1"]);
+            assert_eq!(constants, 6);
+        });
+    }
+
+    #[test]
+    fn test_mut_visitor() {
+        create_session_if_not_set_then(|_| {
+            let mut program = program("import square as s (input = x);\n(x + z) * (x) = (z);\n");
+            Renamer.visit_program_mut(&mut program);
+
+            assert_eq!(program.to_string(), "import square as s (input = w);\n(w + z) * (w) = (z);\n// y = 2 * w\n");
+            assert!(program.span.is_dummy() && program.constraints[0].span.is_dummy());
+            assert!(program.variables().all(|variable| variable.span.is_dummy()));
+        });
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Edit distance computations, used to suggest names for misspelled variables.
+
+use std::cmp;
+
+/// Returns the Levenshtein distance between `a` and `b`,
+/// or `None` if the distance is greater than `limit`.
+pub fn edit_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // The distance is at least the difference in lengths.
+    if a.len().abs_diff(b.len()) > limit {
+        return None;
+    }
+
+    // Only the previous row of the distance matrix is needed to compute the next one.
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, a_char) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(a_char != b_char);
+            curr[j + 1] = cmp::min(substitution, cmp::min(prev[j + 1], curr[j]) + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    let distance = prev[b.len()];
+    (distance <= limit).then_some(distance)
+}
+
+/// Finds the best match for `lookup` among `candidates`.
+///
+/// A candidate that only differs from `lookup` in case is always preferred.
+/// Otherwise, the candidate with the smallest edit distance is returned,
+/// as long as that distance is at most `dist`, or a third of the length of `lookup` if `dist` is `None`.
+/// Candidates that share no characters with `lookup`, such as `x` for `y`, are never suggested.
+pub fn find_best_match_for_name(
+    candidates: impl IntoIterator<Item = impl AsRef<str>>,
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<String> {
+    let limit = dist.unwrap_or_else(|| cmp::max(lookup.chars().count(), 3) / 3);
+
+    let mut best = None;
+    for candidate in candidates {
+        let candidate = candidate.as_ref();
+        if candidate == lookup {
+            continue;
+        }
+        if candidate.to_lowercase() == lookup.to_lowercase() {
+            return Some(candidate.to_string());
+        }
+        if let Some(distance) = edit_distance(candidate, lookup, limit) {
+            if distance >= lookup.chars().count() {
+                continue;
+            }
+            if best.as_ref().map_or(true, |(best_distance, _)| distance < *best_distance) {
+                best = Some((distance, candidate.to_string()));
+            }
+        }
+    }
+
+    best.map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", "", 0), Some(0));
+        assert_eq!(edit_distance("abc", "", 3), Some(3));
+        assert_eq!(edit_distance("kitten", "sitting", 3), Some(3));
+        assert_eq!(edit_distance("kitten", "sitting", 2), None);
+        assert_eq!(edit_distance("sig_a", "sig_b", 1), Some(1));
+    }
+
+    #[test]
+    fn test_find_best_match_for_name() {
+        let candidates = ["sig_A", "sig_b", "out"];
+        assert_eq!(find_best_match_for_name(candidates, "sig_a", None), Some("sig_A".to_string()));
+        assert_eq!(find_best_match_for_name(candidates, "ou", None), Some("out".to_string()));
+        assert_eq!(find_best_match_for_name(candidates, "xyz", None), None);
+        assert_eq!(find_best_match_for_name(candidates, "out", None), None);
+        assert_eq!(find_best_match_for_name(["x"], "y", None), None);
+
+        // The limit counts characters rather than bytes.
+        assert_eq!(find_best_match_for_name(["αδε"], "αβγ", None), None);
+        assert_eq!(find_best_match_for_name(["αβδ"], "αβγ", None), Some("αβδ".to_string()));
+    }
+}
@@ -16,6 +16,8 @@
 
 #![forbid(unsafe_code)]
 
+pub mod edit_distance;
+
 pub mod session_globals;
 
 pub mod span;
@@ -23,4 +25,7 @@ pub use span::Span;
 
 pub mod span_json;
 
+pub mod symbol;
+pub use symbol::{Symbol, SymbolStr};
+
 pub mod source_map;
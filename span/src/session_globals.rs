@@ -14,11 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::source_map::SourceMap;
+use crate::{source_map::SourceMap, symbol::Interner};
 
 /// All the globals for a compiler sessions.
 #[derive(Default)]
 pub struct SessionGlobals {
+    /// The symbol table used in the compiler.
+    pub symbol_interner: Interner,
     /// The source map used in the compiler.
     pub source_map: SourceMap,
 }
@@ -36,6 +38,23 @@ pub fn create_session_if_not_set_then<R>(f: impl FnOnce(&SessionGlobals) -> R) -
     }
 }
 
+/// Creates new session globals, shadowing any existing ones, and then runs the closure `f`.
+/// This gives `f` an empty source map, e.g., when re-analyzing a file that has changed.
+#[inline]
+pub fn create_session_then<R>(f: impl FnOnce(&SessionGlobals) -> R) -> R {
+    let sg = SessionGlobals::default();
+    SESSION_GLOBALS.set(&sg, || SESSION_GLOBALS.with(f))
+}
+
+/// Creates new session globals with the `symbol_interner` of another session, and then runs the closure `f`.
+/// This lets `f` resolve the symbols of that session on another thread, e.g., in a parallel iterator.
+/// The source map of the new session is empty.
+#[inline]
+pub fn create_session_with_symbols_then<R>(symbol_interner: Interner, f: impl FnOnce(&SessionGlobals) -> R) -> R {
+    let sg = SessionGlobals { symbol_interner, source_map: SourceMap::default() };
+    SESSION_GLOBALS.set(&sg, || SESSION_GLOBALS.with(f))
+}
+
 /// Gives access to read or modify the session globals in `f`.
 #[inline]
 pub fn with_session_globals<R>(f: impl FnOnce(&SessionGlobals) -> R) -> R {
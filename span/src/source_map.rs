@@ -260,6 +260,47 @@ impl SourceFile {
         }
     }
 
+    /// Returns the (0-based) line and the (0-based) column of `pos` in UTF-16 code units.
+    /// This is how the Language Server Protocol addresses positions in a document.
+    pub fn utf16_line_col(&self, pos: BytePos) -> (usize, usize) {
+        let line = self.lookup_line(pos).unwrap_or(0);
+        let line_start = self.lines.get(line).copied().unwrap_or(self.start_pos);
+        let mut col = pos.to_usize().saturating_sub(line_start.to_usize());
+
+        // Multi-byte characters take up fewer UTF-16 code units than UTF-8 bytes.
+        for mbc in self.multibyte_chars.iter().filter(|mbc| line_start <= mbc.pos && mbc.pos < pos) {
+            let utf16_len = if mbc.bytes == 4 { 2 } else { 1 };
+            col -= mbc.bytes as usize - utf16_len;
+        }
+
+        (line, col)
+    }
+
+    /// Returns the position at the (0-based) `line` and the (0-based) column `col` in UTF-16 code units,
+    /// or `None` if the line does not exist. Columns past the end of the line are clamped to it.
+    pub fn utf16_line_col_to_byte_pos(&self, line: usize, col: usize) -> Option<BytePos> {
+        let line_start = *self.lines.get(line)?;
+        let line_end = self.lines.get(line + 1).copied().unwrap_or(self.end_pos);
+        let text = &self.src[(line_start - self.start_pos).to_usize()..(line_end - self.start_pos).to_usize()];
+
+        let mut offset = 0;
+        let mut units = 0;
+        for c in text.chars().take_while(|c| *c != '\n') {
+            if units >= col {
+                break;
+            }
+            units += c.len_utf16();
+            offset += c.len_utf8();
+        }
+
+        Some(line_start + BytePos::from_usize(offset))
+    }
+
+    /// Returns whether `span` lies within the file.
+    pub fn contains(&self, span: Span) -> bool {
+        self.start_pos <= span.lo && span.hi <= self.end_pos
+    }
+
     /// Returns contents of a `span` assumed to be within the given file.
     fn contents_of_span(&self, span: Span) -> String {
         let begin_pos = self.bytepos_to_file_charpos(span.lo).to_usize();
@@ -435,3 +476,72 @@ fn analyze_source_file(src: &str, source_file_start_pos: BytePos) -> (Vec<BytePo
 
     (lines, multi_byte_chars)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a file with multi-byte characters, which does not start at the beginning of the source map.
+    fn source_file() -> Rc<SourceFile> {
+        let source_map = SourceMap::default();
+        source_map.new_source("padding", FileName::Custom("padding".into()));
+        // `é` is 2 bytes and `€` is 3 bytes, but both are 1 UTF-16 code unit.
+        // `😀` is 4 bytes, and is 2 UTF-16 code units as a surrogate pair.
+        source_map.new_source("é€😀x\nab\n😀\n", FileName::Custom("test".into()))
+    }
+
+    #[test]
+    fn test_utf16_line_col() {
+        let file = source_file();
+        let pos = |offset| file.start_pos + BytePos(offset);
+
+        assert_eq!(file.utf16_line_col(pos(0)), (0, 0));
+        assert_eq!(file.utf16_line_col(pos(2)), (0, 1));
+        assert_eq!(file.utf16_line_col(pos(5)), (0, 2));
+        assert_eq!(file.utf16_line_col(pos(9)), (0, 4));
+        assert_eq!(file.utf16_line_col(pos(10)), (0, 5));
+        assert_eq!(file.utf16_line_col(pos(12)), (1, 1));
+        assert_eq!(file.utf16_line_col(pos(18)), (2, 2));
+    }
+
+    #[test]
+    fn test_utf16_line_col_to_byte_pos() {
+        let file = source_file();
+        let pos = |offset| Some(file.start_pos + BytePos(offset));
+
+        assert_eq!(file.utf16_line_col_to_byte_pos(0, 0), pos(0));
+        assert_eq!(file.utf16_line_col_to_byte_pos(0, 1), pos(2));
+        assert_eq!(file.utf16_line_col_to_byte_pos(0, 2), pos(5));
+        assert_eq!(file.utf16_line_col_to_byte_pos(0, 4), pos(9));
+        assert_eq!(file.utf16_line_col_to_byte_pos(1, 1), pos(12));
+        assert_eq!(file.utf16_line_col_to_byte_pos(2, 2), pos(18));
+
+        // A column within a surrogate pair moves to the end of the character.
+        assert_eq!(file.utf16_line_col_to_byte_pos(0, 3), pos(9));
+        assert_eq!(file.utf16_line_col_to_byte_pos(2, 1), pos(18));
+    }
+
+    #[test]
+    fn test_utf16_past_the_end() {
+        let file = source_file();
+        let pos = |offset| Some(file.start_pos + BytePos(offset));
+
+        // Columns past the end of a line are clamped to the end of the line, before its newline.
+        assert_eq!(file.utf16_line_col_to_byte_pos(0, 100), pos(10));
+        assert_eq!(file.utf16_line_col_to_byte_pos(1, 3), pos(13));
+        assert_eq!(file.utf16_line_col_to_byte_pos(2, 100), pos(18));
+        // Lines past the end of the file do not exist.
+        assert_eq!(file.utf16_line_col_to_byte_pos(3, 0), None);
+        assert_eq!(file.utf16_line_col_to_byte_pos(100, 0), None);
+    }
+
+    #[test]
+    fn test_utf16_round_trip() {
+        let file = source_file();
+        for (offset, _) in file.src.char_indices() {
+            let pos = file.start_pos + BytePos::from_usize(offset);
+            let (line, col) = file.utf16_line_col(pos);
+            assert_eq!(file.utf16_line_col_to_byte_pos(line, col), Some(pos), "at byte {offset}");
+        }
+    }
+}
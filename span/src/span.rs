@@ -18,7 +18,7 @@
 
 use core::ops::{Add, Sub};
 use serde::{Deserialize, Serialize};
-use std::{fmt, usize};
+use std::fmt;
 
 /// The span type which tracks where formatted errors originate from in a Conlang file.
 /// This is used in many spots throughout the rest of the Conlang crates.
@@ -19,7 +19,7 @@
 use crate::Span;
 
 use serde::{
-    de::{MapAccess, Visitor},
+    de::{Error, MapAccess, Visitor},
     ser::SerializeMap,
     Deserializer,
     Serializer,
@@ -54,7 +54,7 @@ impl<'de> Visitor<'de> for SpanMapVisitor {
     }
 
     fn visit_map<M: MapAccess<'de>>(self, mut access: M) -> Result<Self::Value, M::Error> {
-        let (_, value): (String, Span) = access.next_entry()?.unwrap();
+        let (_, value): (String, Span) = access.next_entry()?.ok_or_else(|| M::Error::missing_field("span"))?;
         Ok(value)
     }
 }
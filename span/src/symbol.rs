@@ -0,0 +1,293 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the conlang library.
+
+// The conlang library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The conlang library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the conlang library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Defines the `Symbol` type, an interned string such as the name of a variable.
+
+use crate::session_globals::{with_session_globals, SESSION_GLOBALS};
+
+use fxhash::FxBuildHasher;
+use indexmap::IndexSet;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    borrow::Borrow,
+    fmt,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+        RwLock,
+    },
+};
+
+/// An interned string, e.g., the name of a variable.
+///
+/// Symbols are compared and hashed by their session and their index in the [`Interner`] of that session,
+/// rather than by their string. The indices of a session are dense, starting with `0` for the empty string,
+/// so that a symbol can index a `Vec`.
+///
+/// A symbol is only resolved in the session that created it, or in one that shares its interner.
+/// Elsewhere, it is shown as `<unknown symbol #N>`, see [`Interner::get`].
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    /// The index of the string in the interner.
+    index: u32,
+    /// The session of the interner, or `0` for the default symbol, which is the empty string in every session.
+    session: u32,
+}
+
+impl Symbol {
+    /// Returns the symbol of `string` in the current session, interning it if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no session is set, since the symbol could not be resolved later.
+    pub fn intern(string: &str) -> Self {
+        with_session_globals(|s| s.symbol_interner.intern(string))
+    }
+
+    /// Returns the string of the symbol in the current session.
+    /// A symbol that cannot be resolved, because it is from another session or no session is set,
+    /// is shown as a placeholder.
+    pub fn as_str(self) -> SymbolStr {
+        SESSION_GLOBALS
+            .is_set()
+            .then(|| with_session_globals(|s| s.symbol_interner.get(self)))
+            .flatten()
+            .unwrap_or_else(|| SymbolStr(format!("<unknown symbol #{}>", self.index).into()))
+    }
+
+    /// Returns the index of the symbol in the interner of its session.
+    pub fn index(self) -> usize {
+        self.index as usize
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.as_str())
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Symbol::intern(&String::deserialize(deserializer)?))
+    }
+}
+
+/// The string of a [`Symbol`], which shares the storage of the interner that it was resolved with.
+/// It keeps the string alive after its session ends, e.g., in an error message.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SymbolStr(Arc<str>);
+
+impl Deref for SymbolStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SymbolStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for SymbolStr {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for SymbolStr {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for SymbolStr {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl fmt::Display for SymbolStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Debug for SymbolStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", &*self.0)
+    }
+}
+
+/// The session of the next interner, to tell the symbols of different sessions apart.
+static NEXT_SESSION: AtomicU32 = AtomicU32::new(1);
+
+/// The symbol table of a session, which maps strings to [`Symbol`]s and back.
+///
+/// The interner owns its strings, so they are freed with the session, unless a [`SymbolStr`] still refers to them.
+/// Clones share the table, so that other threads can resolve the symbols of the session,
+/// see [`create_session_with_symbols_then`](crate::session_globals::create_session_with_symbols_then).
+#[derive(Clone)]
+pub struct Interner {
+    strings: Arc<RwLock<IndexSet<Arc<str>, FxBuildHasher>>>,
+    /// The session of the interner, which its symbols record.
+    session: u32,
+}
+
+impl Default for Interner {
+    /// Returns an interner with only the empty string, which is the default symbol.
+    fn default() -> Self {
+        let mut strings = IndexSet::default();
+        strings.insert(Arc::from(""));
+        Self {
+            strings: Arc::new(RwLock::new(strings)),
+            session: NEXT_SESSION.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+impl Interner {
+    /// Returns the symbol of `string`, interning it if needed.
+    pub fn intern(&self, string: &str) -> Symbol {
+        if let Some(index) = self.strings.read().unwrap().get_index_of(string) {
+            return self.symbol(index);
+        }
+        let (index, _) = self.strings.write().unwrap().insert_full(Arc::from(string));
+        self.symbol(index)
+    }
+
+    /// Returns the string of the `symbol`, or `None` if the `symbol` is from another session.
+    pub fn get(&self, symbol: Symbol) -> Option<SymbolStr> {
+        if symbol.session != 0 && symbol.session != self.session {
+            return None;
+        }
+        self.strings.read().unwrap().get_index(symbol.index()).cloned().map(SymbolStr)
+    }
+
+    /// Returns the number of interned symbols, which bounds the indices of the symbols.
+    pub fn len(&self) -> usize {
+        self.strings.read().unwrap().len()
+    }
+
+    /// Returns whether only the empty string is interned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 1
+    }
+
+    /// Returns the symbol at `index` of this interner.
+    /// The empty string is the default symbol, so that it is equal in every session.
+    fn symbol(&self, index: usize) -> Symbol {
+        match index {
+            0 => Symbol::default(),
+            index => Symbol { index: index as u32, session: self.session },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session_globals::{create_session_then, create_session_with_symbols_then};
+
+    #[test]
+    fn test_intern() {
+        create_session_then(|_| {
+            // The indices are dense, and the default symbol is the empty string.
+            let (x, y) = (Symbol::intern("x"), Symbol::intern("y"));
+            assert_eq!((Symbol::default().index(), x.index(), y.index()), (0, 1, 2));
+            assert_eq!(Symbol::intern("x"), x);
+            assert_eq!([Symbol::default().as_str(), x.as_str(), y.as_str()], ["", "x", "y"]);
+
+            // Another session starts with its own indices and strings, which outlive it through `SymbolStr`s.
+            let (other_y, other_x) = create_session_then(|_| {
+                let (y, x) = (Symbol::intern("y"), Symbol::intern("x"));
+                assert_eq!((y.index(), x.index()), (1, 2));
+                (y.as_str(), x.as_str())
+            });
+            assert_eq!((other_y, other_x), (y.as_str(), x.as_str()));
+        });
+    }
+
+    #[test]
+    fn test_interner_owns_strings() {
+        let interner = Interner::default();
+        let x = interner.intern("x");
+        let string = interner.get(x).unwrap();
+        assert_eq!(Arc::strong_count(&string.0), 2);
+
+        // The strings are freed with the interner, unless they are still resolved.
+        drop(interner);
+        assert_eq!(Arc::strong_count(&string.0), 1);
+    }
+
+    #[test]
+    fn test_symbol_of_another_session() {
+        create_session_then(|_| {
+            let (x, y) = create_session_then(|_| (Symbol::intern("x"), Symbol::intern("y")));
+            let z = Symbol::intern("z");
+
+            // The default symbol is the empty string in every session.
+            assert_eq!(Symbol::default().as_str(), "");
+
+            assert_eq!(Symbol::intern(""), Symbol::default());
+
+            // A symbol of another session is never resolved, even if its index is in bounds in this session.
+            assert_eq!(x.as_str(), "<unknown symbol #1>");
+            assert_eq!(y.as_str(), "<unknown symbol #2>");
+            assert_ne!(x, z);
+            assert_eq!(z.as_str(), "z");
+        });
+
+        // Without a session, no symbol is resolved.
+        assert_eq!(Symbol::default().as_str(), "<unknown symbol #0>");
+    }
+
+    #[test]
+    fn test_shared_interner() {
+        create_session_then(|s| {
+            let x = Symbol::intern("x");
+            let interner = s.symbol_interner.clone();
+            let y = std::thread::spawn(move || {
+                create_session_with_symbols_then(interner, |_| {
+                    assert_eq!(x.as_str(), "x");
+                    Symbol::intern("y")
+                })
+            })
+            .join()
+            .unwrap();
+
+            // The symbols interned by the other thread are resolved in this session.
+            assert_eq!(y.as_str(), "y");
+            assert_eq!(s.symbol_interner.len(), 3);
+        });
+    }
+}